@@ -0,0 +1,221 @@
+//! Non-negative integers of arbitrary size, for answers that outgrow `u64` and `u128`.
+//! Only supports what puzzles usually need: building numbers digit by digit, addition, small
+//! multiplication, comparison and printing.
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+
+/// Limbs are stored in base `10^9`, so printing needs no division.
+const BASE: u32 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+/// An unsigned integer of arbitrary size.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Little-endian limbs without trailing zero limbs, zero is empty.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    #[must_use]
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Builds a number from its decimal digits, most significant first.
+    ///
+    /// # Panics
+    /// If a digit is greater than `9`.
+    pub fn from_digits(digits: impl IntoIterator<Item = u8>) -> Self {
+        let mut n = Self::zero();
+        for digit in digits {
+            assert!(digit < 10, "{digit} is not a decimal digit");
+            n.mul_add_small(10, u32::from(digit));
+        }
+        n
+    }
+
+    /// `self * factor + addend`, in place.
+    pub fn mul_add_small(&mut self, factor: u32, addend: u32) {
+        let mut carry = u64::from(addend);
+        for limb in &mut self.limbs {
+            let value = u64::from(*limb) * u64::from(factor) + carry;
+            *limb = (value % u64::from(BASE)) as u32;
+            carry = value / u64::from(BASE);
+        }
+        self.push_carry(carry);
+        self.normalize();
+    }
+
+    /// The value as a `u64`, if it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        self.limbs.iter().rev().try_fold(0u64, |n, &limb| {
+            n.checked_mul(u64::from(BASE))?.checked_add(u64::from(limb))
+        })
+    }
+
+    fn push_carry(&mut self, mut carry: u64) {
+        while carry > 0 {
+            self.limbs.push((carry % u64::from(BASE)) as u32);
+            carry /= u64::from(BASE);
+        }
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        let mut n = Self::zero();
+        n.push_carry(value);
+        n
+    }
+}
+
+impl PartialEq<u64> for BigUint {
+    fn eq(&self, other: &u64) -> bool {
+        self.to_u64() == Some(*other)
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, rhs: &BigUint) {
+        if self.limbs.len() < rhs.limbs.len() {
+            self.limbs.resize(rhs.limbs.len(), 0);
+        }
+
+        let mut carry = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let value = *limb + rhs.limbs.get(i).copied().unwrap_or(0) + carry;
+            *limb = value % BASE;
+            carry = value / BASE;
+        }
+        self.push_carry(u64::from(carry));
+    }
+}
+
+impl AddAssign for BigUint {
+    fn add_assign(&mut self, rhs: BigUint) {
+        *self += &rhs;
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(mut self, rhs: BigUint) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a BigUint> for BigUint {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |mut sum, n| {
+            sum += n;
+            sum
+        })
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((most, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+
+        write!(f, "{most}")?;
+        for limb in rest.iter().rev() {
+            write!(f, "{limb:0BASE_DIGITS$}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("`{s}` is not a non-negative integer"));
+        }
+        Ok(Self::from_digits(s.bytes().map(|b| b - b'0')))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BigUint;
+
+    #[test]
+    fn prints_numbers() {
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from(1_000_000_007).to_string(), "1000000007");
+        assert_eq!(BigUint::from(u64::MAX).to_string(), u64::MAX.to_string());
+
+        let digits = "1234567890000000000098765432100000000001";
+        assert_eq!(digits.parse::<BigUint>().unwrap().to_string(), digits);
+        assert_eq!("000".parse::<BigUint>(), Ok(BigUint::zero()));
+        assert!("-1".parse::<BigUint>().is_err());
+    }
+
+    #[test]
+    fn adds_numbers() {
+        let max = BigUint::from(u64::MAX);
+        let sum: BigUint = [max.clone(), max.clone(), BigUint::from(2)]
+            .into_iter()
+            .sum();
+        assert_eq!(sum.to_string(), (u128::from(u64::MAX) * 2 + 2).to_string());
+        assert_eq!(sum.to_u64(), None);
+        assert_eq!(max.to_u64(), Some(u64::MAX));
+
+        let mut n = BigUint::from(999_999_999);
+        n.mul_add_small(10, 9);
+        assert_eq!(n.to_string(), "9999999999");
+    }
+
+    #[test]
+    fn compares_numbers() {
+        let small = BigUint::from(999_999_999);
+        let large: BigUint = "1000000000".parse().unwrap();
+        assert!(small < large);
+        assert!(large > BigUint::zero());
+        assert_eq!(large, 1_000_000_000);
+        assert_eq!(large.clone().max(small), large);
+    }
+}
@@ -1,83 +1,67 @@
-use std::str::FromStr;
+use advent_of_code::dial::Dial;
+use advent_of_code::parse::{ParseError, Span, lines};
+use std::num::NonZeroU32;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, parse = parse);
+
+const DIAL_SIZE: NonZeroU32 = NonZeroU32::new(100).unwrap();
+const DIAL_START: i64 = 50;
 
 /// L50 means left 50 steps
 /// R50 means right 50 steps
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum Direction {
-    Left(u16),
-    Right(u16),
+    Left(u32),
+    Right(u32),
 }
 
-impl From<&str> for Direction {
-    fn from(s: &str) -> Self {
-        match s[0..1].to_ascii_lowercase().as_str() {
-            "l" => Direction::Left(s[1..].parse().unwrap()),
-            "r" => Direction::Right(s[1..].parse().unwrap()),
-            _ => panic!("Invalid direction"),
+impl TryFrom<Span<'_>> for Direction {
+    type Error = ParseError;
+
+    fn try_from(span: Span) -> Result<Self, Self::Error> {
+        // the direction is the first character, which may take more than one byte.
+        let first = span.as_str().chars().next().map_or(0, char::len_utf8);
+        let (direction, steps) = span.split_at(first)?;
+        let steps = steps.parse()?;
+        match direction.as_str().to_ascii_lowercase().as_str() {
+            "l" => Ok(Direction::Left(steps)),
+            "r" => Ok(Direction::Right(steps)),
+            _ => Err(direction.error("expected `L` or `R`")),
         }
     }
 }
 
-impl FromStr for Direction {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Direction::from(s))
-    }
-}
-
-fn parse_directions(input: &str) -> Vec<Direction> {
-    input
-        .lines()
+fn parse(input: &str) -> Result<Vec<Direction>, ParseError> {
+    lines(input)
         .filter(|line| !line.is_empty())
-        .map(|line| line.parse().unwrap())
+        .map(Direction::try_from)
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut sum_zeros = 0u16;
-    let mut rotation = 50i16;
-    let directions = parse_directions(input);
-
-    for direction in directions {
-        rotation = match direction {
-            Direction::Left(value) => rotation - (value as i16),
-            Direction::Right(value) => rotation + (value as i16),
-        };
-        if rotation % 100 == 0 {
-            sum_zeros += 1;
+impl Direction {
+    /// Steps to turn the dial, positive to the right.
+    fn steps(self) -> i64 {
+        match self {
+            Direction::Left(value) => -i64::from(value),
+            Direction::Right(value) => i64::from(value),
         }
     }
-    Some(sum_zeros as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut ticks = 0u64;
-    let mut rotation = 50i16;
-    let directions = parse_directions(input);
-
-    for direction in directions {
-        let start = rotation;
-        rotation = match direction {
-            Direction::Left(value) => rotation - (value as i16),
-            Direction::Right(value) => rotation + (value as i16),
-        };
-        if start < rotation {
-            let first = ((start + 1) as f32 / 100.0).ceil() as i16 * 100;
-            if first <= rotation {
-                ticks += ((rotation - first) / 100 + 1) as u64;
-            }
-        } else {
-            let last = ((start - 1) as f32 / 100.0).floor() as i16 * 100;
-            if last >= rotation {
-                ticks += ((last - rotation) / 100 + 1) as u64;
-            }
-        }
+fn turn_dial(directions: &[Direction], size: NonZeroU32, start: i64) -> Dial {
+    let mut dial = Dial::new(size, start);
+    for &direction in directions {
+        dial.rotate(direction.steps());
     }
+    dial
+}
+
+fn part_one(directions: &[Direction]) -> Option<u64> {
+    Some(turn_dial(directions, DIAL_SIZE, DIAL_START).landed_on_zero())
+}
 
-    Some(ticks)
+fn part_two(directions: &[Direction]) -> Option<u64> {
+    Some(turn_dial(directions, DIAL_SIZE, DIAL_START).passed_zero())
 }
 
 #[cfg(test)]
@@ -86,15 +70,20 @@ mod tests {
 
     #[test]
     fn test_parse_direction() {
-        assert_eq!(Direction::from("L50"), Direction::Left(50));
-        assert_eq!(Direction::from("R50"), Direction::Right(50));
+        let parse = |s| Direction::try_from(Span::new(s));
+        assert_eq!(parse("L50"), Ok(Direction::Left(50)));
+        assert_eq!(parse("R50"), Ok(Direction::Right(50)));
+        assert_eq!(parse("X50").unwrap_err().message, "expected `L` or `R`");
+        assert_eq!(parse("É5").unwrap_err().message, "expected `L` or `R`");
+        assert_eq!(parse("L5x").unwrap_err().column, 2);
+        assert!(parse("").is_err());
     }
 
     #[test]
     fn test_parse_directions() {
         assert_eq!(
-            parse_directions("L50\nR50\nL10\nL5\nR55\nR5\nL15\nL4"),
-            vec![
+            parse("L50\nR50\nL10\nL5\nR55\nR5\nL15\nL4"),
+            Ok(vec![
                 Direction::Left(50),
                 Direction::Right(50),
                 Direction::Left(10),
@@ -103,19 +92,33 @@ mod tests {
                 Direction::Right(5),
                 Direction::Left(15),
                 Direction::Left(4),
-            ]
+            ])
         );
     }
 
+    #[test]
+    fn test_dial_settings() {
+        let directions = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let dial = turn_dial(&directions, NonZeroU32::new(10).unwrap(), 0);
+        assert_eq!(dial.position(), 2);
+        assert_eq!(dial.landed_on_zero(), 3);
+
+        let long = parse("R1000000000\nL4000000000\n").unwrap();
+        let dial = turn_dial(&long, DIAL_SIZE, DIAL_START);
+        assert_eq!(dial.passed_zero(), 50_000_000);
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(6));
     }
 }
@@ -1,208 +1,265 @@
-use rayon::prelude::*;
-use std::{ops::Range, str::FromStr};
+use advent_of_code::parse::{ParseError, lines};
+use std::ops::RangeInclusive;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse = parse);
 
 #[derive(Debug, PartialEq)]
-struct Ranges(Vec<Range<u64>>);
-
-impl From<&str> for Ranges {
-    fn from(input: &str) -> Self {
-        let mut ranges = Vec::new();
-        let line = input.lines().take(1).next().unwrap();
-
-        line.split(',').for_each(|range| {
-            let range = range.split('-').collect::<Vec<&str>>();
-            let start = range[0].parse::<u64>().unwrap();
-            let end = range[1].parse::<u64>().unwrap();
-            ranges.push(start..end);
-        });
+struct Ranges(Vec<RangeInclusive<u64>>);
 
-        Ranges(ranges)
-    }
+fn parse(input: &str) -> Result<Ranges, ParseError> {
+    let line = lines(input).expect_line("a list of ranges")?;
+
+    line.split(",")
+        .map(|range| {
+            let (start, end) = range.split_once("-")?;
+            Ok(start.parse()?..=end.parse()?)
+        })
+        .collect::<Result<_, _>>()
+        .map(Ranges)
 }
 
-impl FromStr for Ranges {
-    type Err = Box<dyn std::error::Error>;
+/// How often a block of digits has to repeat for an ID to be invalid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Repeats {
+    Twice,
+    AtLeastTwice,
+}
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from(input))
-    }
+fn pow10(exp: u32) -> u128 {
+    10u128.pow(exp)
 }
 
-trait Dupes
-where
-    Self: Iterator,
-{
-    fn get_dupes(&self) -> Vec<u64>;
-    fn get_repeats(&self) -> Vec<u64>;
+fn digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
 }
 
-impl Dupes for Range<u64> {
-    fn get_dupes(&self) -> Vec<u64> {
-        (self.start..=self.end)
-            .into_par_iter()
-            .flat_map(|i| {
-                let istr = i.to_string();
-                if istr.len() % 2 == 0 {
-                    let (left, right) = istr.split_at(istr.len() / 2);
-                    if left == right {
-                        return Some(i);
-                    }
-                }
-                None
-            })
-            .collect::<Vec<u64>>()
+fn prime_factors(mut n: u32) -> Vec<u32> {
+    let mut factors = vec![];
+    let mut p = 2;
+    while n > 1 {
+        if n.is_multiple_of(p) {
+            factors.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
     }
+    factors
+}
 
-    fn get_repeats(&self) -> Vec<u64> {
-        (self.start..=self.end)
-            .into_par_iter()
-            .flat_map(|i| {
-                let istr = i.to_string();
-                let doubled = format!("{istr}{i}");
-
-                if doubled[1..doubled.len() - 1].contains(&istr) {
-                    return Some(i);
-                }
+/// Sum of all `len`-digit numbers in `lo..=hi` that are a block of `period` digits repeated.
+///
+/// Those are exactly the multiples of `1 + 10^period + 10^(2 * period) + ...` with `len` digits,
+/// so the sum is the multiplier times an arithmetic series over the blocks.
+fn sum_periodic(lo: u128, hi: u128, len: u32, period: u32) -> u128 {
+    let multiplier = (pow10(len) - 1) / (pow10(period) - 1);
+    let first = lo.div_ceil(multiplier);
+    let last = hi / multiplier;
 
-                None
-            })
-            .collect::<Vec<u64>>()
+    if first > last {
+        return 0;
     }
+
+    multiplier * (first + last) * (last - first + 1) / 2
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let ranges = Ranges::from(input);
+/// Sum of all invalid IDs in a range, without visiting the IDs in between.
+fn sum_invalid(range: &RangeInclusive<u64>, repeats: Repeats) -> u128 {
+    let (start, end) = (*range.start(), *range.end());
+    if start > end {
+        return 0;
+    }
 
-    let ids = ranges
-        .0
-        .into_par_iter()
-        .flat_map(|range| range.get_dupes())
-        .sum();
+    (digits(start)..=digits(end))
+        .map(|len| {
+            // the part of the range with exactly `len` digits.
+            let lo = u128::from(start).max(pow10(len - 1));
+            let hi = u128::from(end).min(pow10(len) - 1);
 
-    Some(ids)
-}
+            match repeats {
+                Repeats::Twice if len.is_multiple_of(2) => sum_periodic(lo, hi, len, len / 2),
+                Repeats::Twice => 0,
+                Repeats::AtLeastTwice => {
+                    // every proper period divides `len / q` for a prime factor `q` of `len`.
+                    // numbers with periods `len / q1` and `len / q2` also have period `len / (q1 * q2)`,
+                    // so the union is counted by inclusion–exclusion over sets of prime factors.
+                    let primes = prime_factors(len);
+                    let (mut added, mut removed) = (0, 0);
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let ranges = Ranges::from(input);
+                    for subset in 1..1u32 << primes.len() {
+                        let product: u32 = primes
+                            .iter()
+                            .enumerate()
+                            .filter(|(i, _)| subset & (1 << i) != 0)
+                            .map(|(_, p)| p)
+                            .product();
+                        let sum = sum_periodic(lo, hi, len, len / product);
 
-    let ids = ranges
-        .0
-        .into_par_iter()
-        .flat_map(|range| range.get_repeats())
-        .sum();
+                        if subset.count_ones() % 2 == 1 {
+                            added += sum;
+                        } else {
+                            removed += sum;
+                        }
+                    }
 
-    Some(ids)
+                    added - removed
+                }
+            }
+        })
+        .sum()
+}
+
+fn part_one(ranges: &Ranges) -> Option<u128> {
+    Some(
+        ranges
+            .0
+            .iter()
+            .map(|range| sum_invalid(range, Repeats::Twice))
+            .sum(),
+    )
+}
+
+fn part_two(ranges: &Ranges) -> Option<u128> {
+    Some(
+        ranges
+            .0
+            .iter()
+            .map(|range| sum_invalid(range, Repeats::AtLeastTwice))
+            .sum(),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{Property, equal, inputs};
+
+    /// Checks every ID individually, like the puzzle describes it.
+    fn brute_force(range: RangeInclusive<u64>, repeats: Repeats) -> u128 {
+        range
+            .filter(|i| {
+                let istr = i.to_string();
+                match repeats {
+                    Repeats::Twice => {
+                        let (left, right) = istr.split_at(istr.len() / 2);
+                        istr.len().is_multiple_of(2) && left == right
+                    }
+                    Repeats::AtLeastTwice => {
+                        let doubled = format!("{istr}{istr}");
+                        doubled[1..doubled.len() - 1].contains(&istr)
+                    }
+                }
+            })
+            .map(u128::from)
+            .sum()
+    }
 
     #[test]
     fn test_parse_line() {
         let example: &str = &advent_of_code::template::read_file("examples", DAY);
-        let result = Ranges::from(example);
+        let result = parse(example);
         assert_eq!(
             result,
-            Ranges(vec![
-                Range { start: 11, end: 22 },
-                Range {
-                    start: 95,
-                    end: 115
-                },
-                Range {
-                    start: 998,
-                    end: 1012
-                },
-                Range {
-                    start: 1188511880,
-                    end: 1188511890
-                },
-                Range {
-                    start: 222220,
-                    end: 222224
-                },
-                Range {
-                    start: 1698522,
-                    end: 1698528
-                },
-                Range {
-                    start: 446443,
-                    end: 446449
-                },
-                Range {
-                    start: 38593856,
-                    end: 38593862
-                },
-                Range {
-                    start: 565653,
-                    end: 565659
-                },
-                Range {
-                    start: 824824821,
-                    end: 824824827
-                },
-                Range {
-                    start: 2121212118,
-                    end: 2121212124
-                },
-            ])
+            Ok(Ranges(vec![
+                11..=22,
+                95..=115,
+                998..=1012,
+                1188511880..=1188511890,
+                222220..=222224,
+                1698522..=1698528,
+                446443..=446449,
+                38593856..=38593862,
+                565653..=565659,
+                824824821..=824824827,
+                2121212118..=2121212124,
+            ]))
         );
     }
 
     #[test]
-    fn test_get_dupes() {
-        assert_eq!(Range { start: 11, end: 22 }.get_dupes(), vec![11, 22]);
+    fn test_parse_errors() {
+        let error = parse("11-22,95_115").unwrap_err();
+        assert_eq!(error.message, "expected `-`");
+        assert_eq!((error.line, error.column), (1, 7));
+
+        let error = parse("11-22,95-1x5").unwrap_err();
+        assert_eq!((error.column, error.snippet.as_str()), (10, "1x5"));
+
+        assert_eq!(parse("").unwrap_err().message, "expected a list of ranges");
+    }
+
+    #[test]
+    fn test_sum_dupes() {
+        assert_eq!(sum_invalid(&(11..=22), Repeats::Twice), 11 + 22);
+        assert_eq!(sum_invalid(&(95..=115), Repeats::Twice), 99);
+        assert_eq!(sum_invalid(&(998..=1012), Repeats::Twice), 1010);
+    }
+
+    #[test]
+    fn test_sum_repeats() {
+        assert_eq!(sum_invalid(&(11..=22), Repeats::AtLeastTwice), 11 + 22);
+        assert_eq!(sum_invalid(&(95..=115), Repeats::AtLeastTwice), 99 + 111);
         assert_eq!(
-            Range {
-                start: 95,
-                end: 115
-            }
-            .get_dupes(),
-            vec![99]
+            sum_invalid(&(998..=1012), Repeats::AtLeastTwice),
+            999 + 1010
         );
-        assert_eq!(
-            Range {
-                start: 998,
-                end: 1012
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for range in [1..=9, 1..=150_000, 777_000..=1_300_000, 0..=0] {
+            for repeats in [Repeats::Twice, Repeats::AtLeastTwice] {
+                assert_eq!(
+                    sum_invalid(&range, repeats),
+                    brute_force(range.clone(), repeats),
+                    "{range:?} {repeats:?}"
+                );
             }
-            .get_dupes(),
-            vec![1010]
-        );
+        }
     }
 
     #[test]
-    fn test_get_repeats() {
-        assert_eq!(Range { start: 11, end: 22 }.get_repeats(), vec![11, 22]);
-        assert_eq!(
-            Range {
-                start: 95,
-                end: 115
+    fn test_matches_reference() {
+        Property::new(DAY).check(|case: &inputs::Ranges| {
+            let ranges = parse(&case.to_string()).map_err(|e| e.to_string())?;
+            for (repeats, result) in [
+                (Repeats::Twice, part_one(&ranges)),
+                (Repeats::AtLeastTwice, part_two(&ranges)),
+            ] {
+                let expected = case
+                    .ranges
+                    .iter()
+                    .map(|&(start, end)| brute_force(start..=end, repeats))
+                    .sum();
+                equal(&format!("{repeats:?}"), result, Some(expected))?;
             }
-            .get_repeats(),
-            vec![99, 111]
-        );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_wide_ranges() {
+        // sums over 20-digit IDs do not fit a `u64`.
+        let range = 1..=u64::MAX;
+        assert!(sum_invalid(&range, Repeats::AtLeastTwice) > sum_invalid(&range, Repeats::Twice));
         assert_eq!(
-            Range {
-                start: 998,
-                end: 1012
-            }
-            .get_repeats(),
-            vec![999, 1010]
+            sum_invalid(&(100_000_000_000..=100_001_000_000), Repeats::Twice),
+            100_000_100_000
         );
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(1227775554));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(4174379265));
     }
 }
@@ -1,27 +1,32 @@
-use std::str::FromStr;
+use advent_of_code::bigint::BigUint;
+use advent_of_code::parse::{ParseError, Span, lines};
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, parse = parse);
 
 type Battery = u8;
 #[derive(Debug, Clone)]
 struct Bank(Vec<Battery>);
 
-impl From<&str> for Bank {
-    fn from(input: &str) -> Self {
-        let mut bank = vec![];
-        for c in input.chars() {
-            bank.push(c.to_digit(10).unwrap() as Battery);
-        }
-        Self(bank)
+impl TryFrom<Span<'_>> for Bank {
+    type Error = ParseError;
+
+    fn try_from(span: Span) -> Result<Self, Self::Error> {
+        span.chars()
+            .map(|(c, span)| {
+                c.to_digit(10)
+                    .map(|digit| digit as Battery)
+                    .ok_or_else(|| span.error("expected a digit"))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
     }
 }
 
-impl FromStr for Bank {
-    type Err = String;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from(input))
-    }
+fn parse(input: &str) -> Result<Vec<Bank>, ParseError> {
+    lines(input)
+        .filter(|line| !line.is_empty())
+        .map(Bank::try_from)
+        .collect()
 }
 
 impl Bank {
@@ -40,54 +45,60 @@ impl Bank {
     //     format!("{}{}", a, b).parse::<u64>().unwrap()
     // }
 
-    fn jolts(&self, to_retain: usize) -> u64 {
+    /// The largest number that can be formed by keeping `to_retain` batteries in order.
+    fn jolts(&self, to_retain: usize) -> BigUint {
         let len = self.batteries().len();
         let mut stack: Vec<Battery> = vec![];
-        let mut to_remove = len - to_retain;
+        let mut to_remove = len.saturating_sub(to_retain);
 
         self.batteries().iter().for_each(|num| {
-            while stack.len() > 0 && to_remove > 0 && stack.last() < Some(num) {
+            while !stack.is_empty() && to_remove > 0 && stack.last() < Some(num) {
                 stack.pop();
                 to_remove -= 1;
             }
             stack.push(*num);
         });
 
-        stack
-            .iter()
-            .take(to_retain)
-            .map(|num| num.to_string())
-            .collect::<Vec<_>>()
-            .join("")
-            .parse::<u64>()
-            .unwrap()
+        BigUint::from_digits(stack.into_iter().take(to_retain))
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let banks = input.lines().map(Bank::from).collect::<Vec<_>>();
-    let jolts = banks.iter().map(|b| b.jolts(2)).collect::<Vec<_>>();
-    let sum = jolts.iter().sum::<u64>();
-
-    Some(sum)
+fn total_jolts(banks: &[Bank], to_retain: usize) -> BigUint {
+    banks.iter().map(|b| b.jolts(to_retain)).sum()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let banks = input.lines().map(Bank::from).collect::<Vec<_>>();
-    let jolts = banks.iter().map(|b| b.jolts(12)).collect::<Vec<_>>();
-    let sum = jolts.iter().sum::<u64>();
+fn part_one(banks: &[Bank]) -> Option<BigUint> {
+    Some(total_jolts(banks, 2))
+}
 
-    Some(sum)
+fn part_two(banks: &[Bank]) -> Option<BigUint> {
+    Some(total_jolts(banks, 12))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{Property, equal, inputs};
+
+    /// Tries every subset of `to_retain` batteries.
+    fn brute_force(batteries: &[Battery], to_retain: usize) -> BigUint {
+        let to_retain = to_retain.min(batteries.len());
+        (0u32..1 << batteries.len())
+            .filter(|subset| subset.count_ones() as usize == to_retain)
+            .map(|subset| {
+                let kept = (0..batteries.len())
+                    .filter(|i| subset & (1 << i) != 0)
+                    .map(|i| batteries[i]);
+                BigUint::from_digits(kept)
+            })
+            .max()
+            .unwrap_or_default()
+    }
 
     #[test]
     fn test_parse_bank() {
         let input = "987654321111111";
-        let bank = Bank::from(input);
+        let bank = Bank::try_from(Span::new(input)).unwrap();
         assert_eq!(
             bank.batteries(),
             vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1]
@@ -97,8 +108,7 @@ mod tests {
     #[test]
     fn test_parse_banks() {
         let input: &str = &advent_of_code::template::read_file("examples", DAY);
-        let banks = input.lines().map(Bank::from).collect::<Vec<_>>();
-        dbg!(&banks);
+        let banks = parse(input).unwrap();
         assert_eq!(banks.len(), 4);
         assert_eq!(
             banks[0].batteries(),
@@ -118,38 +128,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("987\n81a\n").unwrap_err();
+        assert_eq!(error.message, "expected a digit");
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
     #[test]
     fn test_bank_jolts() {
         let input = "987654321111111";
-        let bank = Bank::from(input);
+        let bank = Bank::try_from(Span::new(input)).unwrap();
         let jotls = bank.jolts(2);
         assert_eq!(jotls, 98);
 
         let input = "811111111111119";
-        let bank = Bank::from(input);
+        let bank = Bank::try_from(Span::new(input)).unwrap();
         let jotls = bank.jolts(2);
         assert_eq!(jotls, 89);
 
         let input = "987654321111111";
-        let bank = Bank::from(input);
+        let bank = Bank::try_from(Span::new(input)).unwrap();
         let jotls = bank.jolts(12);
         assert_eq!(jotls, 987654321111);
 
         let input = "811111111111119";
-        let bank = Bank::from(input);
+        let bank = Bank::try_from(Span::new(input)).unwrap();
         let jotls = bank.jolts(12);
         assert_eq!(jotls, 811111111119);
     }
 
+    #[test]
+    fn test_jolts_beyond_u64() {
+        let input = "9876543210987654321098765432109";
+        let bank = Bank::try_from(Span::new(input)).unwrap();
+        assert_eq!(bank.jolts(25).to_string(), "9876987654321098765432109");
+        assert_eq!(bank.jolts(40).to_string(), input);
+
+        let banks = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(
+            total_jolts(&banks, 15).to_string(),
+            (987654321111111u64 + 811111111111119 + 234234234234278 + 818181911112111).to_string()
+        );
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        Property::new(DAY).check(|case: &inputs::Banks| {
+            let banks = parse(&case.to_string()).map_err(|e| e.to_string())?;
+            for (to_retain, result) in [(2, part_one(&banks)), (12, part_two(&banks))] {
+                let expected = case.banks.iter().map(|b| brute_force(b, to_retain)).sum();
+                equal(&format!("retaining {to_retain}"), result, Some(expected))?;
+            }
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(357));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result, Some(BigUint::from(357)));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3121910778619));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result, Some(BigUint::from(3121910778619)));
     }
 }
@@ -1,6 +1,8 @@
-use std::{fmt::Display, str::FromStr};
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::parse::ParseError;
+use std::{collections::VecDeque, fmt::Display};
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, parse = parse);
 
 #[derive(Debug, Clone, PartialEq)]
 enum Spot {
@@ -19,156 +21,128 @@ impl Display for Spot {
     }
 }
 
-#[derive(Clone)]
-struct Grid(Vec<Vec<Spot>>);
-
-impl From<&str> for Grid {
-    fn from(input: &str) -> Self {
-        let mut grid = vec![];
-        for line in input.lines() {
-            if line.is_empty() {
-                continue;
-            }
-            let line = line.chars().collect::<Vec<_>>();
-            grid.push(
-                line.into_iter()
-                    .map(|c| match c {
-                        '.' => Spot::Empty,
-                        '@' => Spot::Roll,
-                        _ => unreachable!(),
-                    })
-                    .collect(),
-            );
-        }
-
-        Grid(grid)
-    }
+fn parse(input: &str) -> Result<Grid<Spot>, ParseError> {
+    Grid::try_parse(input, |c| match c {
+        '.' => Some(Spot::Empty),
+        '@' => Some(Spot::Roll),
+        _ => None,
+    })
 }
 
-impl FromStr for Grid {
-    type Err = String;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from(input))
-    }
+fn adjacent(grid: &Grid<Spot>, point: Point) -> Vec<Spot> {
+    grid.neighbours_8(point).map(|p| grid[p].clone()).collect()
 }
 
-const DIRECTIONS: [(i32, i32); 8] = [
-    (0, -1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-    (0, 1),
-    (-1, 1),
-    (-1, 0),
-    (-1, -1),
-];
-
-impl Grid {
-    fn rows_mut(&mut self) -> &mut Vec<Vec<Spot>> {
-        &mut self.0
-    }
+fn accessible_rolls(grid: &mut Grid<Spot>) {
+    let cloned_grid = grid.clone();
 
-    fn adjacent(&self, x: usize, y: usize) -> Vec<Spot> {
-        let mut adjacents: Vec<Spot> = vec![];
-
-        for (dx, dy) in DIRECTIONS {
-            let new_x = x as i32 + dx;
-            let new_y = y as i32 + dy;
-
-            if new_x >= 0
-                && new_x < self.0.len() as i32
-                && new_y >= 0
-                && new_y < self.0[0].len() as i32
-            {
-                let new_row = self.0.get(new_y as usize).unwrap();
-                let new_spot = new_row.get(new_x as usize).unwrap();
-                adjacents.push(new_spot.clone());
+    for (point, spot) in grid.iter_mut() {
+        if *spot == Spot::Roll {
+            let adjacent = adjacent(&cloned_grid, point);
+            if adjacent.iter().filter(|s| *s == &Spot::Roll).count() < 4 {
+                *spot = Spot::AccessibleRoll;
             }
         }
-
-        adjacents
     }
+}
 
-    fn accessible_rolls(&mut self) {
-        let cloned_self = self.clone();
-
-        for (y, row) in self.0.iter_mut().enumerate() {
-            for (x, spot) in row.iter_mut().enumerate() {
-                match spot {
-                    Spot::Roll => {
-                        let adjacent = cloned_self.adjacent(x, y);
-                        if adjacent.iter().filter(|s| *s == &Spot::Roll).count() < 4 {
-                            *spot = Spot::AccessibleRoll;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
+/// The wave in which each roll is removed, if accessible rolls are removed over and over. The first
+/// wave is `1`, empty spots and rolls that are never removed are `None`.
+///
+/// Neighbour counts are computed once, afterwards only the neighbours of removed rolls are revisited.
+fn removal_waves(grid: &Grid<Spot>) -> Grid<Option<u32>> {
+    let is_roll = |point: Point| grid[point] == Spot::Roll;
+
+    let mut counts = Grid::new(grid.width(), grid.height(), 0);
+    for point in grid.positions(|s| *s == Spot::Roll) {
+        counts[point] = grid.neighbours_8(point).filter(|&p| is_roll(p)).count();
     }
 
-    fn remove_accessible_rolls(&mut self) -> usize {
-        let mut removed = 0;
-
-        for row in self.rows_mut().iter_mut() {
-            for spot in row.iter_mut() {
-                match spot {
-                    Spot::AccessibleRoll => {
-                        *spot = Spot::Empty;
-                        removed += 1;
-                    }
-                    _ => {}
-                }
+    let mut waves = Grid::new(grid.width(), grid.height(), None);
+    let mut queue: VecDeque<Point> = grid
+        .positions(|s| *s == Spot::Roll)
+        .filter(|&point| counts[point] < 4)
+        .collect();
+    queue.iter().for_each(|&point| waves[point] = Some(1));
+
+    // rolls are dequeued in wave order, so a roll that becomes accessible now is removed in the next wave.
+    while let Some(point) = queue.pop_front() {
+        let next_wave = waves[point].map_or(1, |wave| wave + 1);
+
+        for neighbour in grid.neighbours_8(point).filter(|&p| is_roll(p)) {
+            counts[neighbour] -= 1;
+            if counts[neighbour] == 3 && waves[neighbour].is_none() {
+                waves[neighbour] = Some(next_wave);
+                queue.push_back(neighbour);
             }
         }
-
-        removed
     }
+
+    waves
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut grid = Grid::from(input);
-    grid.accessible_rolls();
-    let sum = grid
-        .rows_mut()
-        .iter()
-        .flat_map(|row| row.iter().filter(|s| *s == &Spot::AccessibleRoll))
-        .count();
+fn part_one(grid: &Grid<Spot>) -> Option<u64> {
+    let mut grid = grid.clone();
+    accessible_rolls(&mut grid);
+    let sum = grid.positions(|s| *s == Spot::AccessibleRoll).count();
 
     Some(sum as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut grid = Grid::from(input);
-    grid.accessible_rolls();
-    let mut removed = grid.remove_accessible_rolls();
-    let mut total = 0;
-
-    total += removed;
-
-    while removed > 0 {
-        grid.accessible_rolls();
-        removed = grid.remove_accessible_rolls();
-        total += removed;
-    }
+fn part_two(grid: &Grid<Spot>) -> Option<u64> {
+    let removed = removal_waves(grid)
+        .iter()
+        .filter(|(_, w)| w.is_some())
+        .count();
 
-    Some(total as u64)
+    Some(removed as u64)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{Property, equal, inputs::RollGrid};
+
+    /// Removes accessible rolls round by round, returns the rolls removed in the first round and in total.
+    fn brute_force(case: &RollGrid) -> (u64, u64) {
+        let (width, height) = (case.width as isize, case.height() as isize);
+        let mut rolls = case.cells.clone();
+        let mut removed = vec![];
+
+        loop {
+            let accessible: Vec<usize> = (0..rolls.len())
+                .filter(|&i| rolls[i])
+                .filter(|&i| {
+                    let (x, y) = ((i as isize) % width, (i as isize) / width);
+                    let neighbours = (-1..=1)
+                        .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+                        .filter(|&(nx, ny)| (nx, ny) != (x, y))
+                        .filter(|&(nx, ny)| (0..width).contains(&nx) && (0..height).contains(&ny))
+                        .filter(|&(nx, ny)| rolls[(ny * width + nx) as usize])
+                        .count();
+                    neighbours < 4
+                })
+                .collect();
+
+            if accessible.is_empty() {
+                break;
+            }
+            accessible.iter().for_each(|&i| rolls[i] = false);
+            removed.push(accessible.len() as u64);
+        }
+
+        (removed.first().copied().unwrap_or(0), removed.iter().sum())
+    }
 
     #[test]
     fn test_parse_grid() {
         let input: &str = &advent_of_code::template::read_file("examples", DAY);
-        let mut grid = Grid::from(input);
-        assert_eq!(grid.rows_mut().len(), 10);
-        assert_eq!(grid.rows_mut()[0].len(), 10);
+        let grid = parse(input).unwrap();
+        assert_eq!(grid.height(), 10);
+        assert_eq!(grid.width(), 10);
         assert_eq!(
-            grid.rows_mut()[0],
-            vec![
+            grid.row(0),
+            [
                 Spot::Empty,
                 Spot::Empty,
                 Spot::Roll,
@@ -183,17 +157,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("..@\n.#.\n").unwrap_err();
+        assert_eq!(error.message, "unexpected `#`");
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
     #[test]
     fn test_adjacent() {
         let input: &str = &advent_of_code::template::read_file("examples", DAY);
-        let grid = Grid::from(input);
-        let adjacent = grid.adjacent(0, 0);
+        let grid = parse(input).unwrap();
+        let adjacent = adjacent(&grid, (0, 0));
         assert_eq!(adjacent.len(), 3);
         assert_eq!(adjacent[0], Spot::Empty);
         assert_eq!(adjacent[1], Spot::Roll);
         assert_eq!(adjacent[2], Spot::Roll);
 
-        let adjacent = grid.adjacent(7, 0);
+        let adjacent = super::adjacent(&grid, (7, 0));
         assert_eq!(adjacent.len(), 5);
         assert_eq!(adjacent[0], Spot::Roll);
         assert_eq!(adjacent[1], Spot::Roll);
@@ -205,61 +186,89 @@ mod tests {
     #[test]
     fn test_find_accessible_rolls() {
         let input: &str = &advent_of_code::template::read_file("examples", DAY);
-        let mut grid = Grid::from(input);
-        grid.accessible_rolls();
-        assert_eq!(
-            grid.rows_mut()[0]
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(""),
-            "..xx.xx@x."
-        );
-        assert_eq!(
-            grid.rows_mut()[1]
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(""),
-            "x@@.@.@.@@"
-        );
+        let mut grid = parse(input).unwrap();
+        accessible_rolls(&mut grid);
+        assert_eq!(grid.to_string().lines().next().unwrap(), "..xx.xx@x.");
+        assert_eq!(grid.to_string().lines().nth(1).unwrap(), "x@@.@.@.@@");
     }
 
     #[test]
-    fn test_remove_accessible_rolls() {
+    fn test_removal_waves() {
         let input: &str = &advent_of_code::template::read_file("examples", DAY);
-        let mut grid = Grid::from(input);
-        grid.accessible_rolls();
-        let removed = grid.remove_accessible_rolls();
-
-        assert_eq!(
-            grid.rows_mut()[0]
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(""),
-            ".......@.."
+        let waves = removal_waves(&parse(input).unwrap());
+
+        // rolls left after the first wave.
+        let grid = parse(input).unwrap();
+        let remaining = Grid::from_vec(
+            grid.width(),
+            grid.iter()
+                .map(|(point, spot)| match waves[point] {
+                    Some(1) => Spot::Empty,
+                    _ => spot.clone(),
+                })
+                .collect(),
         );
+        assert_eq!(remaining.to_string().lines().next().unwrap(), ".......@..");
+        assert_eq!(remaining.to_string().lines().nth(1).unwrap(), ".@@.@.@.@@");
+
+        assert_eq!(waves.iter().filter(|(_, w)| *w == &Some(1)).count(), 13);
+        assert_eq!(waves.iter().filter(|(_, w)| *w == &Some(2)).count(), 12);
+        assert_eq!(waves.iter().filter(|(_, w)| w.is_some()).count(), 43);
+    }
+
+    #[test]
+    fn test_peels_like_repeated_removal() {
+        let grid = parse("@@@@@@\n@@@@@@\n@@.@@@\n@@@@@@\n@@@@@@\n").unwrap();
+        let waves = removal_waves(&grid);
+        assert_eq!(waves[(0, 0)], Some(1));
+
+        let mut simulated = grid.clone();
+        let mut wave = 0;
+        loop {
+            wave += 1;
+            accessible_rolls(&mut simulated);
+            let removed: Vec<Point> = simulated
+                .positions(|s| *s == Spot::AccessibleRoll)
+                .collect();
+            if removed.is_empty() {
+                break;
+            }
+            for point in removed {
+                assert_eq!(waves[point], Some(wave), "{point:?}");
+                simulated[point] = Spot::Empty;
+            }
+        }
         assert_eq!(
-            grid.rows_mut()[1]
-                .iter()
-                .map(|s| s.to_string())
+            simulated
+                .positions(|s| *s == Spot::Roll)
+                .collect::<Vec<_>>(),
+            grid.positions(|s| *s == Spot::Roll)
+                .filter(|&point| waves[point].is_none())
                 .collect::<Vec<_>>()
-                .join(""),
-            ".@@.@.@.@@"
         );
-        assert_eq!(removed, 13);
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        Property::new(DAY).check(|case: &RollGrid| {
+            let grid = parse(&case.to_string()).map_err(|e| e.to_string())?;
+            let (first_round, total) = brute_force(case);
+            equal("part one", part_one(&grid), Some(first_round))?;
+            equal("part two", part_two(&grid), Some(total))
+        });
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(43));
     }
 }
@@ -1,102 +1,56 @@
-use std::{ops::RangeInclusive, str::FromStr};
+use advent_of_code::intervals::IntervalSet;
+use advent_of_code::parse::{ParseError, lines};
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse);
 
 type Ingredient = u64;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Inventory {
-    fresh: Vec<RangeInclusive<u64>>,
+    fresh: IntervalSet<u64>,
     ingredients: Vec<Ingredient>,
 }
 
-impl From<&str> for Inventory {
-    fn from(input: &str) -> Self {
-        let mut inv: Vec<RangeInclusive<u64>> = Vec::new();
-        let mut ingredients: Vec<Ingredient> = Vec::new();
-        let mut blank = false;
-
-        for line in input.lines() {
-            if line.is_empty() {
-                blank = true;
-                continue;
-            }
-
-            if !blank {
-                let (start, end) = line.split_once('-').unwrap();
-                inv.push(start.parse().unwrap()..=end.parse().unwrap());
-            } else {
-                ingredients.push(line.parse().unwrap());
-            }
-        }
+fn parse(input: &str) -> Result<Inventory, ParseError> {
+    let mut fresh = IntervalSet::new();
+    let mut ingredients: Vec<Ingredient> = Vec::new();
+    let mut lines = lines(input);
 
-        Self {
-            fresh: inv,
-            ingredients,
+    // fresh ranges, up to the first blank line.
+    loop {
+        let line = lines.expect_line("a blank line after the fresh ranges")?;
+        if line.is_empty() {
+            break;
         }
-    }
-}
 
-impl FromStr for Inventory {
-    type Err = std::num::ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from(s))
+        let (start, end) = line.split_once("-")?;
+        fresh.insert(start.parse::<u64>()?..=end.parse()?);
     }
-}
-
-impl Inventory {
-    fn merge_fresh(&mut self) {
-        if self.fresh.is_empty() {
-            return;
-        }
-
-        self.fresh.sort_by_key(|r| *r.start());
-
-        let mut write_idx = 0;
-
-        for read_idx in 1..self.fresh.len() {
-            let (start, end) = (*self.fresh[read_idx].start(), *self.fresh[read_idx].end());
-
-            if start <= self.fresh[write_idx].end() + 1 {
-                let new_end = self.fresh[write_idx].end().max(&end);
-                self.fresh[write_idx] = *self.fresh[write_idx].start()..=*new_end;
-            } else {
-                write_idx += 1;
-                self.fresh[write_idx] = start..=end;
-            }
-        }
 
-        self.fresh.truncate(write_idx + 1);
+    for line in lines.filter(|line| !line.is_empty()) {
+        ingredients.push(line.parse()?);
     }
-}
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut inventory = Inventory::from(input);
-    inventory.merge_fresh();
+    Ok(Inventory { fresh, ingredients })
+}
 
+fn part_one(inventory: &Inventory) -> Option<u64> {
     let fresh = inventory
         .ingredients
         .iter()
-        .filter(|i| inventory.fresh.iter().any(|r| r.contains(i)));
+        .filter(|i| inventory.fresh.contains(**i));
 
     Some(fresh.count() as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut inventory = Inventory::from(input);
-    inventory.merge_fresh();
-    let mut count = 0;
-    for i in &inventory.fresh {
-        count += i.end() - i.start() + 1;
-    }
-
-    Some(count)
+fn part_two(inventory: &Inventory) -> Option<u128> {
+    Some(inventory.fresh.covered_len())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{Property, equal, inputs};
 
     fn input() -> String {
         advent_of_code::template::read_file("examples", DAY)
@@ -104,29 +58,67 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let inventory = Inventory::from(input().as_ref());
+        let inventory = parse(&input()).unwrap();
 
-        assert_eq!(inventory.fresh, vec![3..=5, 10..=14, 16..=20, 12..=18]);
+        assert_eq!(inventory.fresh.len(), 2);
         assert_eq!(inventory.ingredients, vec![1, 5, 8, 11, 17, 32]);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("3-5\n10_14\n\n1\n").unwrap_err();
+        assert_eq!(error.message, "expected `-`");
+        assert_eq!(error.line, 2);
+
+        let error = parse("3-5\n\n1\nx\n").unwrap_err();
+        assert_eq!((error.line, error.snippet.as_str()), (4, "x"));
+
+        let error = parse("3-5\n10-14").unwrap_err();
+        assert_eq!(
+            error.message,
+            "expected a blank line after the fresh ranges"
+        );
+        assert_eq!((error.line, error.column), (2, 6));
+    }
+
     #[test]
     fn test_merge_ranges() {
-        let mut inventory = Inventory::from(input().as_ref());
-        inventory.merge_fresh();
+        let inventory = parse(&input()).unwrap();
 
-        assert_eq!(inventory.fresh, vec![3..=5, 10..=20]);
+        assert_eq!(
+            inventory.fresh.iter().collect::<Vec<_>>(),
+            vec![3..=5, 10..=20]
+        );
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        Property::new(DAY).check(|case: &inputs::Inventory| {
+            let inventory = parse(&case.to_string()).map_err(|e| e.to_string())?;
+            let is_fresh = |id: &u64| {
+                case.ranges
+                    .iter()
+                    .any(|(start, end)| (start..=end).contains(&id))
+            };
+
+            let fresh = case.ingredients.iter().filter(|id| is_fresh(id)).count();
+            equal("part one", part_one(&inventory), Some(fresh as u64))?;
+
+            let max = case.ranges.iter().map(|&(_, end)| end).max().unwrap_or(0);
+            let covered = (0..=max).filter(is_fresh).count();
+            equal("part two", part_two(&inventory), Some(covered as u128))
+        });
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&input());
+        let result = part_one(&parse(&input()).unwrap());
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&input());
+        let result = part_two(&parse(&input()).unwrap());
         assert_eq!(result, Some(14));
     }
 }
@@ -1,5 +1,7 @@
-advent_of_code::solution!(6);
-use std::str::FromStr;
+use advent_of_code::layout::TextBlock;
+use advent_of_code::parse::{ParseError, Span, end_of_input};
+
+advent_of_code::solution!(6, parse = parse);
 
 #[derive(Debug, PartialEq, Clone)]
 enum Op {
@@ -18,46 +20,22 @@ struct Problems(Vec<Problem>);
 #[derive(Debug, PartialEq, Clone)]
 struct ProblemsRtl(Vec<Problem>);
 
-impl From<&str> for Problems {
-    fn from(input: &str) -> Self {
-        let mut problems = Vec::new();
-
-        for (idx, line) in input.lines().enumerate() {
-            if line.is_empty() {
-                continue;
-            }
-
-            if idx == 0 {
-                for num in line.split_whitespace().map(|s| s.parse().unwrap()) {
-                    problems.push(Problem {
-                        numbers: vec![num],
-                        op: Op::Add,
-                    });
-                }
-            } else {
-                for (col, val) in line.trim().split_whitespace().enumerate() {
-                    if let Ok(num) = val.parse() {
-                        problems[col].numbers.push(num);
-                    } else {
-                        problems[col].op = match val {
-                            "+" => Op::Add,
-                            "*" => Op::Mul,
-                            _ => unreachable!(),
-                        };
-                    }
-                }
-            }
-        }
-
-        Self(problems)
-    }
+/// The same worksheet, read left-to-right by rows and right-to-left by columns.
+#[derive(Debug, PartialEq, Clone)]
+struct Worksheet {
+    problems: Problems,
+    problems_rtl: ProblemsRtl,
 }
 
-impl FromStr for Problems {
-    type Err = std::num::ParseIntError;
+impl TryFrom<Span<'_>> for Op {
+    type Error = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from(s))
+    fn try_from(span: Span) -> Result<Self, Self::Error> {
+        match span.as_str() {
+            "+" => Ok(Op::Add),
+            "*" => Ok(Op::Mul),
+            _ => Err(span.error("expected `+` or `*`")),
+        }
     }
 }
 
@@ -73,64 +51,6 @@ impl Problems {
     }
 }
 
-impl From<&str> for ProblemsRtl {
-    fn from(input: &str) -> Self {
-        let lines: Vec<&str> = input.lines().collect();
-        let op_line = lines.last().expect("No operation line");
-        let number_lines = &lines[..lines.len() - 1];
-        let width = number_lines.iter().map(|l| l.len()).max().unwrap_or(0);
-        let mut problems = Vec::new();
-        let mut problem_numbers: Vec<u64> = Vec::new();
-
-        for col in (0..width).rev() {
-            let mut numbers: Vec<char> = Vec::new();
-
-            for row in number_lines {
-                if let Some(ch) = row.chars().nth(col) {
-                    numbers.push(ch);
-                }
-            }
-            let num_str = numbers.iter().collect::<String>();
-            if num_str.trim().is_empty() {
-                problems.push(Problem {
-                    numbers: problem_numbers.clone(),
-                    op: Op::Add,
-                });
-                problem_numbers.clear();
-            } else {
-                let num: u64 = num_str.trim().parse().unwrap();
-                problem_numbers.push(num);
-            }
-        }
-        problems.push(Problem {
-            numbers: problem_numbers,
-            op: Op::Add,
-        });
-
-        for (col, op) in op_line.split_whitespace().rev().enumerate() {
-            match op {
-                "+" => {
-                    problems[col].op = Op::Add;
-                }
-                "*" => {
-                    problems[col].op = Op::Mul;
-                }
-                _ => unreachable!(),
-            }
-        }
-
-        Self(problems)
-    }
-}
-
-impl FromStr for ProblemsRtl {
-    type Err = std::num::ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from(s))
-    }
-}
-
 impl ProblemsRtl {
     fn do_homework(&self) -> Vec<u64> {
         self.0
@@ -143,15 +63,58 @@ impl ProblemsRtl {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let problems = Problems::from(input);
-    let results = problems.do_homework();
+/// Every problem is a block of columns with its operator in the last row. Read by rows, the numbers
+/// are written left-to-right, read by columns they are written top-to-bottom and right-to-left.
+fn parse(input: &str) -> Result<Worksheet, ParseError> {
+    let text = TextBlock::parse(input);
+    if text.height() == 0 {
+        return Err(end_of_input(input).error("expected a worksheet"));
+    }
+
+    let mut problems = vec![];
+    let mut problems_rtl = vec![];
+
+    for block in text.blocks() {
+        let (numbers, op) = block
+            .split_last_row()
+            .ok_or_else(|| end_of_input(input).error("expected a row of operators"))?;
+        if numbers.height() == 0 {
+            return Err(op.trim().error("expected numbers above the operator"));
+        }
+        let op = Op::try_from(op.trim())?;
+
+        problems.push(Problem {
+            numbers: numbers
+                .rows()
+                .map(|row| row.trim().parse())
+                .collect::<Result<_, _>>()?,
+            op: op.clone(),
+        });
+        problems_rtl.push(Problem {
+            numbers: numbers
+                .transposed()
+                .rev()
+                .map(|column| column.parse())
+                .collect::<Result<_, _>>()?,
+            op,
+        });
+    }
+
+    problems_rtl.reverse();
+
+    Ok(Worksheet {
+        problems: Problems(problems),
+        problems_rtl: ProblemsRtl(problems_rtl),
+    })
+}
+
+fn part_one(worksheet: &Worksheet) -> Option<u64> {
+    let results = worksheet.problems.do_homework();
     Some(results.iter().sum())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let problems = ProblemsRtl::from(input);
-    let results = problems.do_homework();
+fn part_two(worksheet: &Worksheet) -> Option<u64> {
+    let results = worksheet.problems_rtl.do_homework();
     Some(results.iter().sum())
 }
 
@@ -165,7 +128,7 @@ mod tests {
 
     #[test]
     fn test_parse_problems() {
-        let problems = Problems::from(input().as_ref());
+        let problems = parse(&input()).unwrap().problems;
         assert_eq!(problems.0.len(), 4);
         assert_eq!(problems.0[0].numbers.len(), 3);
         assert_eq!(problems.0[0].numbers, vec![123, 45, 6]);
@@ -176,7 +139,7 @@ mod tests {
 
     #[test]
     fn test_do_homework() {
-        let problems = Problems::from(input().as_ref());
+        let problems = parse(&input()).unwrap().problems;
         let results = problems.do_homework();
         assert_eq!(results.len(), 4);
         assert_eq!(results[0], 33210);
@@ -187,7 +150,7 @@ mod tests {
 
     #[test]
     fn test_parse_rtl_problems() {
-        let problems = ProblemsRtl::from(input().as_ref());
+        let problems = parse(&input()).unwrap().problems_rtl;
         assert_eq!(problems.0.len(), 4);
         assert_eq!(problems.0[0].numbers.len(), 3);
         assert_eq!(problems.0[0].numbers, vec![4, 431, 623]);
@@ -198,7 +161,7 @@ mod tests {
 
     #[test]
     fn test_do_homework_rtl() {
-        let problems = ProblemsRtl::from(input().as_ref());
+        let problems = parse(&input()).unwrap().problems_rtl;
         let results = problems.do_homework();
         assert_eq!(results.len(), 4);
         assert_eq!(results[0], 1058);
@@ -207,15 +170,35 @@ mod tests {
         assert_eq!(results[3], 8544);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("12 3\n4  x\n*  +\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+
+        let error = parse("12 3\n4\n*  +\n").unwrap_err();
+        assert!(error.message.starts_with("invalid value ``"));
+        assert_eq!(error.line, 2);
+
+        let error = parse("12 3\n4  6\n*  -\n").unwrap_err();
+        assert_eq!(error.message, "expected `+` or `*`");
+        assert_eq!((error.line, error.column), (3, 4));
+
+        let error = parse("*  +\n").unwrap_err();
+        assert_eq!(error.message, "expected numbers above the operator");
+        assert_eq!((error.line, error.column), (1, 1));
+
+        assert_eq!(parse("").unwrap_err().message, "expected a worksheet");
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&input());
+        let result = part_one(&parse(&input()).unwrap());
         assert_eq!(result, Some(4277556));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&input());
+        let result = part_two(&parse(&input()).unwrap());
         assert_eq!(result, Some(3263827));
     }
 }
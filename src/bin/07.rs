@@ -1,74 +1,52 @@
-advent_of_code::solution!(7);
-use std::{collections::HashMap, fmt::Display, str::FromStr};
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::parse::{ParseError, end_of_input, lines};
+use std::fmt::Display;
+
+advent_of_code::solution!(7, parse = parse);
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Spot {
     Start,
     Empty,
     Splitter,
-    Tachyon,
-}
-
-#[derive(Debug, Clone)]
-struct TachyonTimeline {
-    index: usize,
-    weight: usize,
 }
 
 #[derive(Debug, Clone)]
-struct Row(Vec<Spot>);
-
-#[derive(Debug)]
-struct Grid {
-    rows: Vec<Row>,
-    row: usize,
-    split_count: usize,
-    timelines: Vec<TachyonTimeline>,
+struct Manifold {
+    grid: Grid<Spot>,
+    /// Both parts read their answer from the same sweep of the beams.
+    sweep: Sweep,
 }
 
-impl From<&str> for Grid {
-    fn from(input: &str) -> Self {
-        let rows: Vec<Row> = input
-            .lines()
-            .map(|line| {
-                Row(line
-                    .chars()
-                    .map(|c| match c {
-                        '.' => Spot::Empty,
-                        '^' => Spot::Splitter,
-                        'S' => Spot::Start,
-                        'T' => Spot::Tachyon,
-                        _ => unreachable!(),
-                    })
-                    .collect())
-            })
-            .collect();
-
-        let (start_pos, _) = rows[0]
-            .0
-            .iter()
-            .enumerate()
-            .find(|(_, s)| *s == &Spot::Start)
-            .unwrap();
-
-        Self {
-            rows,
-            row: 0,
-            split_count: 0,
-            timelines: vec![TachyonTimeline {
-                index: start_pos,
-                weight: 1,
-            }],
-        }
-    }
+/// Results of moving the beams from the start down to the last row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Sweep {
+    splits: u64,
+    timelines: u128,
 }
 
-impl FromStr for Grid {
-    type Err = String;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from(input))
-    }
+fn parse(input: &str) -> Result<Manifold, ParseError> {
+    let grid = Grid::try_parse(input, |c| match c {
+        '.' => Some(Spot::Empty),
+        '^' => Some(Spot::Splitter),
+        'S' => Some(Spot::Start),
+        _ => None,
+    })?;
+
+    let start = grid.find(&Spot::Start).ok_or_else(|| {
+        lines(input)
+            .find(|line| !line.is_empty())
+            .unwrap_or_else(|| end_of_input(input))
+            .error("expected a start `S`")
+    })?;
+
+    let beams = Beams::new(&grid, start).run();
+    let sweep = Sweep {
+        splits: beams.splits,
+        timelines: beams.timelines(),
+    };
+
+    Ok(Manifold { grid, sweep })
 }
 
 impl Display for Spot {
@@ -77,99 +55,97 @@ impl Display for Spot {
             Spot::Start => write!(f, "S"),
             Spot::Empty => write!(f, "."),
             Spot::Splitter => write!(f, "^"),
-            Spot::Tachyon => write!(f, "|"),
         }
     }
 }
 
-impl Display for Row {
+impl Display for Manifold {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.0.iter().map(|s| s.to_string()).collect::<String>()
-        )
+        write!(f, "{}", self.grid)
     }
 }
 
-impl Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.rows
-                .iter()
-                .map(|r| r.to_string())
-                .collect::<Vec<String>>()
-                .join("\n")
-        )
-    }
+/// Beams moving down through the manifold one row at a time.
+///
+/// Instead of tracking every timeline, each column holds the number of timelines whose beam is
+/// in that column of the current row. A splitter sends the weight of its column to both sides,
+/// so a full sweep takes `O(rows × width)`.
+#[derive(Debug, Clone)]
+struct Beams<'a> {
+    grid: &'a Grid<Spot>,
+    row: usize,
+    weights: Vec<u128>,
+    next: Vec<u128>,
+    splits: u64,
+    /// Timelines whose beam left the manifold through the left or right edge.
+    exited: u128,
 }
 
-impl Grid {
-    fn run(&mut self) {
-        if self.row == self.rows.len() - 1 {
-            return;
+impl<'a> Beams<'a> {
+    fn new(grid: &'a Grid<Spot>, start: Point) -> Self {
+        let width = grid.width();
+        let (x, row) = start;
+
+        let mut weights = vec![0; width];
+        weights[x] = 1;
+
+        Beams {
+            grid,
+            row,
+            weights,
+            next: vec![0; width],
+            splits: 0,
+            exited: 0,
         }
-        let indices = self.rows[self.row]
-            .0
-            .iter()
-            .enumerate()
-            .filter_map(|(i, s)| match s {
-                Spot::Start | Spot::Tachyon => Some(i),
-                _ => None,
-            })
-            .collect::<Vec<usize>>();
-
-        for i in indices {
-            if self.rows[self.row + 1].0[i] == Spot::Splitter {
-                self.split_count += 1;
-                self.rows[self.row + 1].0[i - 1] = Spot::Tachyon;
-                self.rows[self.row + 1].0[i + 1] = Spot::Tachyon;
-                let timeline = self.timelines.iter_mut().find(|t| t.index == i).unwrap();
-                timeline.index -= 1;
-                let weight = timeline.weight;
-                self.timelines.push(TachyonTimeline {
-                    index: i + 1,
-                    weight: weight,
-                });
-                self.timelines.sort_by_key(|t| t.index);
-
-                let mut weight_map: HashMap<usize, usize> = HashMap::new();
-                for timeline in self.timelines.iter() {
-                    *weight_map.entry(timeline.index).or_insert(0) += timeline.weight;
-                }
+    }
 
-                let mut write_pos = 0;
-                for (index, weight) in weight_map.into_iter() {
-                    self.timelines[write_pos] = TachyonTimeline { index, weight };
-                    write_pos += 1;
-                }
+    /// Moves all beams down by one row. Returns `false` once the beams reached the last row.
+    fn step(&mut self) -> bool {
+        if self.row + 1 >= self.grid.height() {
+            return false;
+        }
+
+        self.row += 1;
+        self.next.fill(0);
+        let row = self.grid.row(self.row);
+
+        for (x, &weight) in self.weights.iter().enumerate().filter(|(_, w)| **w > 0) {
+            if row[x] != Spot::Splitter {
+                self.next[x] += weight;
+                continue;
+            }
 
-                self.timelines.truncate(write_pos);
-            } else {
-                self.rows[self.row + 1].0[i] = Spot::Tachyon;
+            self.splits += 1;
+            for side in [x.checked_sub(1), Some(x + 1).filter(|&x| x < row.len())] {
+                match side {
+                    Some(side) => self.next[side] += weight,
+                    None => self.exited += weight,
+                }
             }
         }
 
-        self.row += 1;
+        std::mem::swap(&mut self.weights, &mut self.next);
+        true
     }
-}
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut grid = Grid::from(input);
-    while grid.row < grid.rows.len() - 1 {
-        grid.run();
+    /// Moves the beams down until they reach the last row.
+    fn run(mut self) -> Self {
+        while self.step() {}
+        self
     }
-    Some(grid.split_count as u64)
-}
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut grid = Grid::from(input);
-    while grid.row < grid.rows.len() - 1 {
-        grid.run();
+    /// Number of timelines, counting those that left through an edge.
+    fn timelines(&self) -> u128 {
+        self.exited + self.weights.iter().sum::<u128>()
     }
-    Some(grid.timelines.iter().map(|t| t.weight).sum::<usize>() as u64)
+}
+
+fn part_one(manifold: &Manifold) -> Option<u64> {
+    Some(manifold.sweep.splits)
+}
+
+fn part_two(manifold: &Manifold) -> Option<u128> {
+    Some(manifold.sweep.timelines)
 }
 
 #[cfg(test)]
@@ -180,41 +156,96 @@ mod tests {
         advent_of_code::template::read_file("examples", DAY)
     }
 
+    fn beams(manifold: &Manifold) -> Beams<'_> {
+        Beams::new(&manifold.grid, manifold.grid.find(&Spot::Start).unwrap())
+    }
+
     #[test]
     fn test_parse() {
-        let grid = Grid::from(input().as_str());
-        assert_eq!(grid.rows.len(), 16);
-        assert_eq!(grid.rows[0].to_string(), ".......S.......");
+        let manifold = parse(&input()).unwrap();
+        assert_eq!(manifold.grid.height(), 16);
+        assert_eq!(manifold.grid.find(&Spot::Start), Some((7, 0)));
+        assert_eq!(manifold.to_string().lines().next(), Some(".......S......."));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("..S..\n..^..\n.x...\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+
+        let error = parse(".....\n..^..\n").unwrap_err();
+        assert_eq!(error.message, "expected a start `S`");
+        assert_eq!(error.line, 1);
     }
 
     #[test]
     fn test_run() {
-        let mut grid = Grid::from(input().as_str());
-        grid.run();
-        grid.run();
-        assert_eq!(grid.rows[0].to_string(), ".......S.......");
-        assert_eq!(grid.rows[1].to_string(), ".......|.......");
-        assert_eq!(grid.rows[2].to_string(), "......|^|......");
+        let manifold = parse(&input()).unwrap();
+        let mut beams = beams(&manifold);
+        assert!(beams.step());
+        assert!(beams.step());
+
+        let columns: Vec<usize> = (0..manifold.grid.width())
+            .filter(|&x| beams.weights[x] > 0)
+            .collect();
+        assert_eq!(beams.row, 2);
+        assert_eq!(columns, vec![6, 8]);
+        assert_eq!(beams.splits, 1);
     }
 
     #[test]
     fn test_full_run() {
-        let mut grid = Grid::from(input().as_str());
-        while grid.row < grid.rows.len() - 1 {
-            grid.run();
+        let manifold = parse(&input()).unwrap();
+        let beams = beams(&manifold).run();
+        assert_eq!(beams.row, manifold.grid.height() - 1);
+        assert_eq!(beams.splits, 21);
+        assert_eq!(beams.timelines(), 40);
+        assert_eq!(
+            manifold.sweep,
+            Sweep {
+                splits: 21,
+                timelines: 40
+            }
+        );
+    }
+
+    #[test]
+    fn test_edges() {
+        let manifold = parse("S..\n^..\n.^.\n..^\n").unwrap();
+        let beams = beams(&manifold).run();
+        assert_eq!(beams.splits, 3);
+        // one beam leaves to the left of the first splitter, one to the right of the last one.
+        assert_eq!(beams.exited, 2);
+        assert_eq!(beams.timelines(), 4);
+
+        let single_row = parse("S\n").unwrap();
+        assert_eq!(part_two(&single_row), Some(1));
+    }
+
+    #[test]
+    fn test_deep_manifolds() {
+        // every row splits every beam, doubling the timelines beyond `u64`.
+        let mut rows = vec![format!("{}S{}", ".".repeat(70), ".".repeat(70))];
+        for y in 0..70 {
+            let row: String = (0..141)
+                .map(|x| if x % 2 == y % 2 { '^' } else { '.' })
+                .collect();
+            rows.push(row);
         }
-        assert_eq!(grid.split_count, 21);
+        let manifold = parse(&rows.join("\n")).unwrap();
+
+        assert_eq!(part_two(&manifold), Some(1 << 70));
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&input());
+        let result = part_one(&parse(&input()).unwrap());
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&input());
+        let result = part_two(&parse(&input()).unwrap());
         assert_eq!(result, Some(40));
     }
 }
@@ -0,0 +1,146 @@
+//! A circular dial with numbered positions, e.g. a safe's combination lock.
+//! The dial counts how often it points at zero, using exact integer arithmetic for any number of steps.
+use std::num::NonZeroU32;
+
+/// A dial with positions `0..size`. Rotating right increases the position, rotating left decreases it,
+/// both wrap around.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dial {
+    size: i64,
+    /// Always in `0..size`.
+    position: i64,
+    landed_on_zero: u64,
+    passed_zero: u64,
+}
+
+impl Dial {
+    /// Creates a dial with `size` positions pointing at `start`, which wraps around if it is out of range.
+    pub fn new(size: NonZeroU32, start: i64) -> Self {
+        let size = i64::from(size.get());
+        Dial {
+            size,
+            position: start.rem_euclid(size),
+            landed_on_zero: 0,
+            passed_zero: 0,
+        }
+    }
+
+    /// The position the dial points at.
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// Rotates the dial by `steps` clicks, right if positive and left if negative.
+    pub fn rotate(&mut self, steps: i64) {
+        // widened, so that no rotation can overflow.
+        let (size, position) = (i128::from(self.size), i128::from(self.position));
+        let target = position + i128::from(steps);
+
+        // the positions visited are `position + 1..=target` to the right and `target..=position - 1` to the left,
+        // count the multiples of `size` among them.
+        let passed = if steps >= 0 {
+            target.div_euclid(size) - position.div_euclid(size)
+        } else {
+            (position - 1).div_euclid(size) - (target - 1).div_euclid(size)
+        };
+
+        self.position = i64::try_from(target.rem_euclid(size)).expect("positions are below `size`");
+        self.passed_zero += u64::try_from(passed).expect("zero crossings are non-negative");
+        if self.position == 0 {
+            self.landed_on_zero += 1;
+        }
+    }
+
+    /// How many rotations ended with the dial pointing at zero, including rotations by zero steps.
+    pub fn landed_on_zero(&self) -> u64 {
+        self.landed_on_zero
+    }
+
+    /// How many clicks of all rotations made the dial point at zero, including the last click of a rotation.
+    pub fn passed_zero(&self) -> u64 {
+        self.passed_zero
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Dial;
+    use std::num::NonZeroU32;
+
+    fn dial(size: u32, start: i64) -> Dial {
+        Dial::new(NonZeroU32::new(size).unwrap(), start)
+    }
+
+    /// Rotates the dial one click at a time.
+    fn clicks(size: i64, start: i64, rotations: &[i64]) -> (u64, u64) {
+        let (mut position, mut landed, mut passed) = (start.rem_euclid(size), 0, 0);
+        for &steps in rotations {
+            for _ in 0..steps.unsigned_abs() {
+                position = (position + steps.signum()).rem_euclid(size);
+                if position == 0 {
+                    passed += 1;
+                }
+            }
+            if position == 0 {
+                landed += 1;
+            }
+        }
+        (landed, passed)
+    }
+
+    #[test]
+    fn wraps_around() {
+        let mut dial = dial(100, 150);
+        assert_eq!(dial.position(), 50);
+
+        dial.rotate(-68);
+        assert_eq!(dial.position(), 82);
+        dial.rotate(18);
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.landed_on_zero(), 1);
+        assert_eq!(dial.passed_zero(), 2);
+
+        dial.rotate(1000);
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.passed_zero(), 12);
+
+        // staying at zero counts as landing on it, but does not pass it.
+        dial.rotate(0);
+        assert_eq!(dial.landed_on_zero(), 3);
+        assert_eq!(dial.passed_zero(), 12);
+    }
+
+    #[test]
+    fn matches_clicking() {
+        let rotations = [
+            -68, -30, 48, -5, 60, -55, -1, -99, 14, -82, 0, 300, -301, 7, -7,
+        ];
+        for size in [1, 2, 3, 7, 100] {
+            for start in [-3, 0, 1, 50] {
+                let mut dial = dial(size, start);
+                rotations.iter().for_each(|&steps| dial.rotate(steps));
+                assert_eq!(
+                    (dial.landed_on_zero(), dial.passed_zero()),
+                    clicks(i64::from(size), start, &rotations),
+                    "size {size}, start {start}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn handles_long_rotations() {
+        let mut dial = dial(100, 0);
+        dial.rotate(i64::MAX);
+        assert_eq!(dial.position(), i64::MAX % 100);
+        assert_eq!(dial.passed_zero(), (i64::MAX / 100) as u64);
+
+        dial.rotate(i64::MIN);
+        assert_eq!(
+            dial.position(),
+            (i64::MAX % 100 + i64::MIN % 100).rem_euclid(100)
+        );
+    }
+}
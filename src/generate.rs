@@ -0,0 +1,333 @@
+//! Synthetic puzzle inputs of any size, to find out how solutions behave on inputs larger than the real one.
+//!
+//! Every day with a [`Generator`] can write inputs in the format of its puzzle. The same day, size and
+//! seed always produce the same input.
+use crate::property::{
+    Rng,
+    inputs::{Banks, Inventory, Ranges, RollGrid},
+};
+use crate::template::Day;
+
+/// Generates inputs for one day.
+pub trait Generator {
+    /// What `size` counts, e.g. `"rotations"`.
+    fn unit(&self) -> &'static str;
+
+    /// An input with `size` units, e.g. a grid of `size` × `size` cells.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+}
+
+/// The generator of a day, if there is one.
+pub fn generator(day: Day) -> Option<&'static dyn Generator> {
+    match day.into_inner() {
+        1 => Some(&Rotations),
+        2 => Some(&IdRanges),
+        3 => Some(&BatteryBanks),
+        4 => Some(&PaperRolls),
+        5 => Some(&Ingredients),
+        6 => Some(&Worksheet),
+        7 => Some(&Manifold),
+        _ => None,
+    }
+}
+
+/// Generates an input for a day, [`None`] if the day has no generator.
+pub fn generate(day: Day, size: usize, seed: u64) -> Option<String> {
+    generator(day).map(|generator| generator.generate(&mut Rng::new(seed), size))
+}
+
+#[must_use]
+pub fn get_generated_path(day: Day, size: usize) -> String {
+    format!("data/generated/{day}-{size}.txt")
+}
+
+/// Day 01: one dial rotation per line, e.g. `L68`.
+pub struct Rotations;
+
+impl Generator for Rotations {
+    fn unit(&self) -> &'static str {
+        "rotations"
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let direction = if rng.chance(50) { 'L' } else { 'R' };
+                format!("{direction}{}\n", rng.range(1..=999))
+            })
+            .collect()
+    }
+}
+
+/// Day 02: comma-separated ID ranges of up to ten digits on a single line.
+pub struct IdRanges;
+
+impl Generator for IdRanges {
+    fn unit(&self) -> &'static str {
+        "ranges"
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let ranges = (0..size.max(1))
+            .map(|_| {
+                let digits = rng.range(1..=10) as u32;
+                let start = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+                (start, start + rng.range(0..=100_000))
+            })
+            .collect();
+
+        Ranges { ranges }.to_string()
+    }
+}
+
+/// Day 03: banks of 100 batteries each.
+pub struct BatteryBanks;
+
+impl Generator for BatteryBanks {
+    fn unit(&self) -> &'static str {
+        "banks"
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let banks = (0..size)
+            .map(|_| (0..100).map(|_| rng.range(1..=9) as u8).collect())
+            .collect();
+
+        Banks { banks }.to_string()
+    }
+}
+
+/// Day 04: a square grid with `size` rows, about two thirds of the spots hold a roll.
+pub struct PaperRolls;
+
+impl Generator for PaperRolls {
+    fn unit(&self) -> &'static str {
+        "rows"
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let width = size.max(1);
+        let cells = (0..width * width).map(|_| rng.chance(66)).collect();
+
+        RollGrid { width, cells }.to_string()
+    }
+}
+
+/// Day 05: `size` fresh ranges of wide IDs, a blank line, then `size` ingredient IDs.
+pub struct Ingredients;
+
+impl Generator for Ingredients {
+    fn unit(&self) -> &'static str {
+        "ranges and ingredients"
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        const MAX_ID: u64 = 1_000_000_000_000_000;
+
+        let ranges = (0..size.max(1))
+            .map(|_| {
+                let start = rng.range(1..=MAX_ID);
+                (start, start + rng.range(0..=MAX_ID / 10_000))
+            })
+            .collect();
+        let ingredients = (0..size).map(|_| rng.range(1..=MAX_ID)).collect();
+
+        Inventory {
+            ranges,
+            ingredients,
+        }
+        .to_string()
+    }
+}
+
+/// Day 06: a worksheet of `size` problems, each with four numbers written in a column and an operator below.
+pub struct Worksheet;
+
+impl Generator for Worksheet {
+    fn unit(&self) -> &'static str {
+        "problems"
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        const ROWS: usize = 4;
+        let mut rows = vec![String::new(); ROWS + 1];
+
+        for i in 0..size.max(1) {
+            let mut numbers: Vec<String> =
+                (0..ROWS).map(|_| rng.range(1..=999).to_string()).collect();
+            // read by columns, a column must not have a gap between digits, so the numbers are
+            // ordered by length.
+            numbers.sort_by_key(String::len);
+            if rng.chance(50) {
+                numbers.reverse();
+            }
+            let width = numbers.iter().map(String::len).max().unwrap_or(1);
+            // numbers of a problem are either all left- or all right-aligned.
+            let left_aligned = rng.chance(50);
+            let op = if rng.chance(50) { '+' } else { '*' };
+
+            for (row, number) in rows.iter_mut().zip(&numbers) {
+                if i > 0 {
+                    row.push(' ');
+                }
+                if left_aligned {
+                    row.push_str(&format!("{number:<width$}"));
+                } else {
+                    row.push_str(&format!("{number:>width$}"));
+                }
+            }
+
+            let operators = &mut rows[ROWS];
+            if i > 0 {
+                operators.push(' ');
+            }
+            operators.push_str(&format!("{op:<width$}"));
+        }
+
+        rows.iter().map(|row| format!("{row}\n")).collect()
+    }
+}
+
+/// Day 07: a manifold with `size` rows, the start in the middle of the first row and splitters on every
+/// other row, within reach of the beam.
+pub struct Manifold;
+
+impl Generator for Manifold {
+    fn unit(&self) -> &'static str {
+        "rows"
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let height = size.max(1);
+        // odd, so that the start is centered.
+        let width = height | 1;
+        let center = width / 2;
+
+        (0..height)
+            .map(|y| {
+                let row: String = (0..width)
+                    .map(|x| {
+                        // the `k`-th row of splitters is reached by beams at odd offsets from the
+                        // center if `k` is even, and at even offsets if `k` is odd.
+                        let (k, offset) = (y / 2, x.abs_diff(center));
+                        let reachable = k > 0 && offset < k && (offset + k) % 2 == 1;
+                        if y == 0 && x == center {
+                            'S'
+                        } else if y % 2 == 0 && reachable && rng.chance(75) {
+                            '^'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                format!("{row}\n")
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generate, generator, get_generated_path};
+    use crate::day;
+    use crate::grid::Grid;
+    use crate::layout::TextBlock;
+    use crate::parse::lines;
+
+    #[test]
+    fn generates_deterministically() {
+        for day in 1..=7 {
+            let day = crate::template::Day::new(day).unwrap();
+            let input = generate(day, 20, 7).unwrap();
+            assert_eq!(Some(input.clone()), generate(day, 20, 7), "day {day}");
+            assert_ne!(Some(input), generate(day, 20, 8), "day {day}");
+        }
+        assert!(generator(day!(12)).is_none());
+    }
+
+    #[test]
+    fn respects_sizes() {
+        assert_eq!(generate(day!(1), 30, 1).unwrap().lines().count(), 30);
+        assert_eq!(generate(day!(2), 30, 1).unwrap().split(',').count(), 30);
+        assert_eq!(generate(day!(3), 30, 1).unwrap().lines().count(), 30);
+
+        let grid = generate(day!(4), 30, 1).unwrap();
+        assert!(grid.lines().all(|row| row.len() == 30));
+        assert_eq!(grid.lines().count(), 30);
+
+        let inventory = generate(day!(5), 30, 1).unwrap();
+        let (fresh, ingredients) = inventory.split_once("\n\n").unwrap();
+        assert_eq!(fresh.lines().count(), 30);
+        assert_eq!(ingredients.lines().count(), 30);
+
+        let worksheet = generate(day!(6), 30, 1).unwrap();
+        let operators = worksheet.lines().last().unwrap();
+        assert_eq!(operators.split_whitespace().count(), 30);
+        assert!(worksheet.lines().all(|row| row.len() == operators.len()));
+
+        let manifold = generate(day!(7), 30, 1).unwrap();
+        assert_eq!(manifold.lines().count(), 30);
+        assert_eq!(manifold.lines().next().unwrap().find('S'), Some(15));
+
+        assert_eq!(get_generated_path(day!(4), 30), "data/generated/04-30.txt");
+    }
+
+    #[test]
+    fn generates_parsable_inputs() {
+        // the parsers of the days live in their bins, these checks follow them with the same helpers.
+        for seed in 1..=3 {
+            let input = |day| generate(day, 50, seed).unwrap();
+
+            for line in lines(&input(day!(1))) {
+                let (direction, steps) = line.split_at(1).unwrap();
+                assert!(matches!(direction.as_str(), "L" | "R"), "{line:?}");
+                steps.parse::<u32>().unwrap();
+            }
+
+            for range in lines(&input(day!(2))).next().unwrap().split(",") {
+                let (start, end) = range.split_once("-").unwrap();
+                assert!(start.parse::<u64>().unwrap() <= end.parse::<u64>().unwrap());
+            }
+
+            for bank in lines(&input(day!(3))) {
+                assert_eq!(bank.as_str().len(), 100);
+                assert!(bank.chars().all(|(c, _)| ('1'..='9').contains(&c)));
+            }
+
+            let rolls = Grid::try_parse(&input(day!(4)), |c| matches!(c, '.' | '@').then_some(c));
+            assert_eq!(rolls.unwrap().height(), 50);
+
+            let inventory = input(day!(5));
+            let (fresh, ingredients) = inventory.split_once("\n\n").unwrap();
+            for range in lines(fresh) {
+                let (start, end) = range.split_once("-").unwrap();
+                assert!(start.parse::<u64>().unwrap() <= end.parse::<u64>().unwrap());
+            }
+            for ingredient in lines(ingredients) {
+                ingredient.parse::<u64>().unwrap();
+            }
+
+            let worksheet = input(day!(6));
+            let text = TextBlock::parse(&worksheet);
+            assert_eq!(text.blocks().count(), 50);
+            for block in text.blocks() {
+                let (numbers, op) = block.split_last_row().unwrap();
+                assert!(matches!(op.trim().as_str(), "+" | "*"));
+                for row in numbers.rows() {
+                    row.trim().parse::<u64>().unwrap();
+                }
+                for column in numbers.transposed() {
+                    column.parse::<u64>().unwrap();
+                }
+            }
+
+            let manifold = Grid::try_parse(&input(day!(7)), |c| {
+                matches!(c, '.' | '^' | 'S').then_some(c)
+            })
+            .unwrap();
+            assert_eq!(manifold.iter().filter(|(_, c)| **c == 'S').count(), 1);
+        }
+    }
+}
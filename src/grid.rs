@@ -0,0 +1,460 @@
+//! Dense two-dimensional grid, e.g. for puzzles that come as a character map.
+//! Points are `(x, y)` tuples, with `(0, 0)` being the top left corner.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::{ParseError, lines};
+
+/// A point on a grid, `(x, y)`.
+pub type Point = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours, clockwise starting north.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours, clockwise starting north.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// If the number of cells is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of width {width}",
+            cells.len()
+        );
+
+        Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Parses a character map, mapping every character to a cell. Empty lines are skipped.
+    ///
+    /// # Panics
+    /// If the lines are not of equal length.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let line_width = cells.len() - before;
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) => assert_eq!(width, line_width, "grid rows differ in length"),
+            }
+        }
+
+        match width {
+            Some(width) => Grid::from_vec(width, cells),
+            None => Grid {
+                width: 0,
+                height: 0,
+                cells,
+            },
+        }
+    }
+
+    /// Parses a character map like [`Grid::parse`], but reports characters that do not map to a cell
+    /// and rows of differing length as a [`ParseError`]. Empty lines are skipped.
+    pub fn try_parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for line in lines(input).filter(|line| !line.is_empty()) {
+            for (c, span) in line.chars() {
+                cells.push(cell(c).ok_or_else(|| span.error(format!("unexpected `{c}`")))?);
+            }
+
+            let line_width = line.as_str().chars().count();
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(line.error(format!("expected a row of width {width}")));
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(match width {
+            Some(width) => Grid::from_vec(width, cells),
+            None => Grid {
+                width: 0,
+                height: 0,
+                cells,
+            },
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, (x, y): Point) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.in_bounds(point)
+            .then(|| &self.cells[point.1 * self.width + point.0])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.in_bounds(point)
+            .then(|| &mut self.cells[point.1 * self.width + point.0])
+    }
+
+    /// Moves `point` by `(dx, dy)`, returns [`None`] if the result is outside the grid.
+    pub fn step(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        let point = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.in_bounds(point).then_some(point)
+    }
+
+    /// The orthogonal neighbours of a point, clockwise starting north.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS_4
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// All eight neighbours of a point, clockwise starting north.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS_8
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// All cells of the grid with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// All cells of the grid with their points, row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a chunk size of zero.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// # Panics
+    /// If `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is out of bounds for width {}",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Diagonals running from top left to bottom right, starting with the bottom left one.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (1..self.height)
+            .rev()
+            .map(|y| (0, y))
+            .chain((0..self.width).map(|x| (x, 0)));
+
+        starts.map(|start| self.ray(start, (1, 1)))
+    }
+
+    /// Diagonals running from top right to bottom left, starting with the top left one.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .map(|x| (x, 0))
+            .chain((1..self.height).filter_map(|y| Some((self.width.checked_sub(1)?, y))));
+
+        starts.map(|start| self.ray(start, (-1, 1)))
+    }
+
+    /// Cells from `start` (inclusive) in a direction until the edge of the grid.
+    pub fn ray(&self, start: Point, direction: (isize, isize)) -> impl Iterator<Item = &T> {
+        std::iter::successors(self.in_bounds(start).then_some(start), move |point| {
+            self.step(*point, direction)
+        })
+        .map(|point| &self[point])
+    }
+
+    /// The point of the first cell, row by row, that matches a predicate.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The points of all cells that match a predicate, row by row.
+    pub fn positions(&self, predicate: impl Fn(&T) -> bool) -> impl Iterator<Item = Point> {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// Creates a grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The point of the first cell, row by row, equal to `value`.
+    pub fn find(&self, value: &T) -> Option<Point> {
+        self.position(|cell| cell == value)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`. A grid without columns has no rows either.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        let height = if width == 0 { 0 } else { height };
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, i.e. rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |(x, y)| (y, x))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self {
+        let height = self.height;
+        self.remap(height, self.width, |(x, y)| (y, height - 1 - x))
+    }
+
+    /// Rotates the grid by 90° counterclockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, width, |(x, y)| (width - 1 - y, x))
+    }
+
+    /// Creates a grid of the given size whose cell at `point` is the cell of `self` at `source(point)`.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(Point) -> Point) -> Self {
+        Grid {
+            width,
+            height,
+            cells: (0..width * height)
+                .map(|i| self[source((i % width, i / width))].clone())
+                .collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("point {point:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("point {point:?} is outside the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c)
+    }
+
+    fn collect<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn parses_character_maps() {
+        let grid = Grid::parse("#.\n\n.#\n", |c| c == '#');
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 2);
+        assert!(grid[(0, 0)]);
+        assert!(!grid[(1, 0)]);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(Grid::parse("", |c| c).height(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is out of bounds for width 3")]
+    fn panics_for_columns_out_of_bounds() {
+        grid().column(3);
+    }
+
+    #[test]
+    fn creates_empty_grids() {
+        let grid = Grid::new(0, 5, '.');
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(Grid::new(2, 3, '.').height(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_ragged_rows() {
+        Grid::parse("ab\nc", |c| c);
+    }
+
+    #[test]
+    fn reports_malformed_maps() {
+        let parse = |input| Grid::try_parse(input, |c| matches!(c, '#' | '.').then_some(c == '#'));
+        assert_eq!(parse("#.\n.#\n").map(|grid| grid.height()), Ok(2));
+
+        let error = parse("#.\n.x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.snippet, "x");
+
+        let error = parse("#.\n#\n").unwrap_err();
+        assert_eq!(error.message, "expected a row of width 2");
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours_8((1, 0)).collect::<Vec<_>>(),
+            vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]
+        );
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 5);
+        assert_eq!(grid.step((0, 0), (-1, 0)), None);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(collect(grid.column(1)), "be");
+        assert_eq!(
+            grid.columns().map(collect).collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.diagonals().map(collect).collect::<Vec<_>>(),
+            ["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(collect).collect::<Vec<_>>(),
+            ["a", "bd", "ce", "f"]
+        );
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = grid();
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+        assert_eq!(grid.find(&'z'), None);
+        assert_eq!(
+            grid.positions(|c| "aeiou".contains(*c)).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn mutates_cells() {
+        let mut grid = Grid::new(2, 2, 0);
+        grid[(1, 0)] = 1;
+        grid.row_mut(1)[0] = 2;
+        grid.iter_mut()
+            .filter(|((x, y), _)| x == y)
+            .for_each(|(_, cell)| *cell = 3);
+        assert_eq!(grid.to_string(), "31\n23");
+    }
+}
@@ -0,0 +1,313 @@
+//! Sets of integers stored as sorted, disjoint intervals.
+//! Useful for puzzles that deal in (possibly overlapping) ranges, e.g. `3-5,10-14,12-18`.
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+/// Integer types an [`IntervalSet`] can hold.
+pub trait Integer: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// `self + 1`, or [`None`] on overflow.
+    fn successor(self) -> Option<Self>;
+
+    /// `self - 1`, or [`None`] on overflow.
+    fn predecessor(self) -> Option<Self>;
+
+    /// `hi - lo` for `lo <= hi`, which always fits in a `u128`.
+    fn distance(lo: Self, hi: Self) -> u128;
+}
+
+macro_rules! impl_integer {
+    ($( $t:ty => $u:ty ),*) => {
+        $(
+            impl Integer for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                #[allow(clippy::cast_sign_loss, clippy::cast_lossless)]
+                fn distance(lo: Self, hi: Self) -> u128 {
+                    hi.wrapping_sub(lo) as $u as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+/// A set of integers, stored as sorted intervals that neither overlap nor touch.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Inclusive `(start, end)` pairs.
+    intervals: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the set contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of disjoint intervals in the set.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Number of values in the set, saturating at [`u128::MAX`].
+    pub fn covered_len(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(|&(start, end)| T::distance(start, end).saturating_add(1))
+            .fold(0, u128::saturating_add)
+    }
+
+    /// Adds a range to the set, coalescing it with overlapping and adjacent intervals.
+    pub fn insert(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = to_inclusive(&range) else {
+            return;
+        };
+
+        // first interval that ends at or after `start - 1`.
+        let from = self
+            .intervals
+            .partition_point(|&(_, e)| e.successor().is_some_and(|e| e < start));
+        // first interval that starts after `end + 1`.
+        let to = self
+            .intervals
+            .partition_point(|&(s, _)| end.successor().is_none_or(|end| s <= end));
+
+        let merged = if from < to {
+            (
+                start.min(self.intervals[from].0),
+                end.max(self.intervals[to - 1].1),
+            )
+        } else {
+            (start, end)
+        };
+
+        self.intervals.splice(from..to, [merged]);
+    }
+
+    /// Removes a range from the set, splitting intervals that it partially covers.
+    pub fn remove(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = to_inclusive(&range) else {
+            return;
+        };
+
+        // first interval that ends at or after `start`.
+        let from = self.intervals.partition_point(|&(_, e)| e < start);
+        // first interval that starts after `end`.
+        let to = self.intervals.partition_point(|&(s, _)| s <= end);
+
+        if from >= to {
+            return;
+        }
+
+        let (first_start, _) = self.intervals[from];
+        let (_, last_end) = self.intervals[to - 1];
+
+        let left = start
+            .predecessor()
+            .filter(|_| first_start < start)
+            .map(|e| (first_start, e));
+        let right = end
+            .successor()
+            .filter(|_| last_end > end)
+            .map(|s| (s, last_end));
+
+        self.intervals
+            .splice(from..to, left.into_iter().chain(right));
+    }
+
+    /// Returns `true` if the set contains `value`. Runs in `O(log n)`.
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|&(_, e)| e < value);
+        self.intervals
+            .get(index)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Returns `true` if the set contains every value of `range`. Runs in `O(log n)`.
+    pub fn contains_range(&self, range: impl RangeBounds<T>) -> bool {
+        let Some((start, end)) = to_inclusive(&range) else {
+            return true;
+        };
+
+        let index = self.intervals.partition_point(|&(_, e)| e < start);
+        self.intervals
+            .get(index)
+            .is_some_and(|&(s, e)| s <= start && end <= e)
+    }
+
+    /// All values that are in `self` or `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        other.iter().for_each(|range| set.insert(range));
+        set
+    }
+
+    /// All values that are in both `self` and `other`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// All values that are in `self` but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        other.iter().for_each(|range| set.remove(range));
+        set
+    }
+
+    /// The intervals of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+}
+
+/// Converts any range to inclusive bounds, returns [`None`] if it is empty.
+fn to_inclusive<T: Integer>(range: &impl RangeBounds<T>) -> Option<(T, T)> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.successor()?,
+        Bound::Unbounded => T::MIN,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&end) => end.predecessor()?,
+        Bound::Unbounded => T::MAX,
+    };
+
+    (start <= end).then_some((start, end))
+}
+
+impl<T: Integer, R: RangeBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Integer, R: RangeBounds<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|range| self.insert(range));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::IntervalSet;
+    use std::ops::RangeInclusive;
+
+    fn ranges<T: super::Integer>(set: &IntervalSet<T>) -> Vec<RangeInclusive<T>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn coalesces_on_insert() {
+        let set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(ranges(&set), vec![3..=5, 10..=20]);
+
+        let mut set = set;
+        set.insert(6..10);
+        assert_eq!(ranges(&set), vec![3..=20]);
+
+        set.insert(25..25);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn removes_ranges() {
+        let mut set: IntervalSet<i32> = [-10..=10, 20..=30].into_iter().collect();
+        set.remove(0..25);
+        assert_eq!(ranges(&set), vec![-10..=-1, 25..=30]);
+
+        set.remove(..=-5);
+        assert_eq!(ranges(&set), vec![-4..=-1, 25..=30]);
+
+        set.remove(..);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn queries_containment() {
+        let set: IntervalSet<u8> = [3..=5, 10..=20].into_iter().collect();
+        assert!(set.contains(3));
+        assert!(set.contains(20));
+        assert!(!set.contains(8));
+        assert!(!set.contains(21));
+        assert!(set.contains_range(11..15));
+        assert!(!set.contains_range(4..=10));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: IntervalSet<u32> = [0..=10, 20..=30].into_iter().collect();
+        let b: IntervalSet<u32> = [5..=25].into_iter().collect();
+
+        assert_eq!(ranges(&a.union(&b)), vec![0..=30]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![5..=10, 20..=25]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..=4, 26..=30]);
+        assert_eq!(ranges(&b.difference(&a)), vec![11..=19]);
+    }
+
+    #[test]
+    fn handles_type_bounds() {
+        let mut set: IntervalSet<i8> = [i8::MIN..=-1, 1..=i8::MAX].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.covered_len(), 255);
+
+        set.insert(0..=0);
+        assert_eq!(ranges(&set), vec![i8::MIN..=i8::MAX]);
+        assert_eq!(set.covered_len(), 256);
+
+        let full: IntervalSet<u128> = [..].into_iter().collect::<IntervalSet<u128>>();
+        assert_eq!(full.covered_len(), u128::MAX);
+    }
+}
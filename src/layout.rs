@@ -0,0 +1,295 @@
+//! Fixed-width text layouts, e.g. worksheets with numbers written in aligned or vertical columns.
+//! The text is split into blocks at columns that are blank in every row. Rows may differ in length,
+//! missing characters at the end of a row count as spaces.
+//!
+//! ```text
+//! 123 328      the first block has the rows `123`, ` 45` and `  6`,
+//!  45 64       transposed its columns read `1  `, `24 ` and `356`.
+//!   6 98
+//! ```
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+use crate::parse::{ParseError, Span, lines};
+
+/// A block of text, addressed by `(column, row)` in characters.
+#[derive(Clone, Debug)]
+pub struct TextBlock<'a> {
+    rows: Vec<Span<'a>>,
+    /// Byte offset of every character of a row, followed by the length of the row.
+    offsets: Vec<Vec<usize>>,
+    width: usize,
+}
+
+impl<'a> TextBlock<'a> {
+    /// Creates a text block from rows. Trailing blank rows are dropped.
+    pub fn new(rows: impl IntoIterator<Item = Span<'a>>) -> Self {
+        let mut rows: Vec<Span> = rows.into_iter().collect();
+        while rows.last().is_some_and(|row| row.trim().is_empty()) {
+            rows.pop();
+        }
+
+        let offsets: Vec<Vec<usize>> = rows
+            .iter()
+            .map(|row| {
+                let text = row.as_str();
+                text.char_indices()
+                    .map(|(i, _)| i)
+                    .chain([text.len()])
+                    .collect()
+            })
+            .collect();
+
+        let width = offsets.iter().map(|o| o.len() - 1).max().unwrap_or(0);
+
+        TextBlock {
+            rows,
+            offsets,
+            width,
+        }
+    }
+
+    /// Creates a text block from all lines of an input.
+    pub fn parse(input: &'a str) -> Self {
+        Self::new(lines(input))
+    }
+
+    /// Number of columns of the longest row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The character at `(x, y)`, [`None`] if the row ends before `x`.
+    pub fn get(&self, x: usize, y: usize) -> Option<(char, Span<'a>)> {
+        let span = self.slice(x..x + 1, y)?;
+        span.as_str().chars().next().map(|c| (c, span))
+    }
+
+    /// The characters of a row that fall into `columns`. Empty if the row ends before the columns.
+    ///
+    /// # Panics
+    /// If `y` is out of bounds.
+    pub fn row(&self, y: usize, columns: Range<usize>) -> Span<'a> {
+        let offsets = &self.offsets[y];
+        let end = offsets.len() - 1;
+        let range = offsets[columns.start.min(end)]..offsets[columns.end.min(end)];
+        self.rows[y]
+            .get(range)
+            .expect("offsets are char boundaries")
+    }
+
+    /// All rows, read left-to-right.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = Span<'a>> + '_ {
+        self.rows.iter().copied()
+    }
+
+    /// Returns `true` if column `x` contains only whitespace in every row.
+    pub fn is_blank_column(&self, x: usize) -> bool {
+        (0..self.height()).all(|y| self.get(x, y).is_none_or(|(c, _)| c.is_whitespace()))
+    }
+
+    /// Splits the text into blocks at columns that are blank in every row. Reverse the iterator
+    /// to read the blocks right-to-left.
+    pub fn blocks(&self) -> impl DoubleEndedIterator<Item = Block<'_, 'a>> {
+        // a single pass over all characters finds the columns that are in use.
+        let mut used = vec![false; self.width];
+        for row in &self.rows {
+            for (x, c) in row.as_str().chars().enumerate() {
+                used[x] |= !c.is_whitespace();
+            }
+        }
+
+        let mut blocks = vec![];
+        let mut start = None;
+        for x in 0..=self.width {
+            match (start, used.get(x).copied().unwrap_or(false)) {
+                (None, true) => start = Some(x),
+                (Some(s), false) => {
+                    blocks.push(Block {
+                        text: self,
+                        columns: s..x,
+                        rows: 0..self.height(),
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        blocks.into_iter()
+    }
+
+    fn slice(&self, columns: Range<usize>, y: usize) -> Option<Span<'a>> {
+        let offsets = self.offsets.get(y)?;
+        let range = *offsets.get(columns.start)?..*offsets.get(columns.end)?;
+        self.rows[y].get(range)
+    }
+}
+
+/// A rectangular part of a [`TextBlock`].
+#[derive(Clone, Debug)]
+pub struct Block<'t, 'a> {
+    text: &'t TextBlock<'a>,
+    columns: Range<usize>,
+    rows: Range<usize>,
+}
+
+impl<'t, 'a> Block<'t, 'a> {
+    /// The columns of the text block this block covers.
+    pub fn columns(&self) -> Range<usize> {
+        self.columns.clone()
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The rows of the block, read left-to-right.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = Span<'a>> + '_ {
+        self.rows
+            .clone()
+            .map(|y| self.text.row(y, self.columns.clone()))
+    }
+
+    /// The columns of the block, each read top-to-bottom. Reverse the iterator to read the
+    /// columns right-to-left.
+    pub fn transposed(&self) -> impl DoubleEndedIterator<Item = Column<'a>> + '_ {
+        // points at the column in the first row, in case the column has no characters at all.
+        let first_row = self.rows.start.min(self.text.height() - 1);
+
+        self.columns.clone().map(move |x| Column {
+            cells: self.rows.clone().map(|y| self.text.get(x, y)).collect(),
+            anchor: self.text.row(first_row, x..x),
+        })
+    }
+
+    /// Splits off the last row of the block, e.g. an operator written below a column of numbers.
+    pub fn split_last_row(&self) -> Option<(Self, Span<'a>)> {
+        let last = self.rows.clone().next_back()?;
+        let block = Block {
+            rows: self.rows.start..last,
+            ..self.clone()
+        };
+        Some((block, self.text.row(last, self.columns.clone())))
+    }
+}
+
+/// A column of a [`Block`], read top-to-bottom.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Column<'a> {
+    /// [`None`] where a row ends before the column.
+    cells: Vec<Option<(char, Span<'a>)>>,
+    anchor: Span<'a>,
+}
+
+impl<'a> Column<'a> {
+    /// The characters of the column, padding counts as spaces.
+    pub fn chars(&self) -> impl DoubleEndedIterator<Item = char> + '_ {
+        self.cells.iter().map(|cell| cell.map_or(' ', |(c, _)| c))
+    }
+
+    /// Returns `true` if the column contains only whitespace.
+    pub fn is_blank(&self) -> bool {
+        self.chars().all(char::is_whitespace)
+    }
+
+    /// Parses the column with [`FromStr`], ignoring leading and trailing whitespace.
+    /// Errors point at the first character of the column that is not whitespace.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let text: String = self.chars().collect();
+        let text = text.trim();
+
+        text.parse().map_err(|e| {
+            let message = format!("invalid value `{text}`: {e}");
+            self.cells
+                .iter()
+                .flatten()
+                .find(|(c, _)| !c.is_whitespace())
+                .map_or(self.anchor, |&(_, span)| span)
+                .error(message)
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::TextBlock;
+
+    const WORKSHEET: &str = "123 328  51 64\n 45 64  387 23\n  6 98  215 314\n*   +   *   +  \n\n";
+
+    #[test]
+    fn splits_blocks() {
+        let text = TextBlock::parse(WORKSHEET);
+        assert_eq!(text.height(), 4);
+        assert_eq!(text.width(), 15);
+        assert!(text.is_blank_column(3));
+        assert!(!text.is_blank_column(14));
+
+        let columns: Vec<_> = text.blocks().map(|block| block.columns()).collect();
+        assert_eq!(columns, vec![0..3, 4..7, 8..11, 12..15]);
+
+        let last = text.blocks().next_back().unwrap();
+        let rows: Vec<_> = last.rows().map(|row| row.as_str()).collect();
+        assert_eq!(rows, vec!["64", "23", "314", "+  "]);
+        assert_eq!(last.rows().nth(2).unwrap().column(), 13);
+    }
+
+    #[test]
+    fn reads_transposed() {
+        let text = TextBlock::parse(WORKSHEET);
+        let (numbers, op) = text.blocks().next_back().unwrap().split_last_row().unwrap();
+        assert_eq!(op.trim().as_str(), "+");
+        assert_eq!(numbers.height(), 3);
+
+        let columns: Vec<u64> = numbers
+            .transposed()
+            .rev()
+            .map(|column| column.parse().unwrap())
+            .collect();
+        assert_eq!(columns, vec![4, 431, 623]);
+
+        let column = numbers.transposed().next_back().unwrap();
+        assert_eq!(column.chars().collect::<String>(), "  4");
+        assert!(!column.is_blank());
+    }
+
+    #[test]
+    fn reports_columns() {
+        let text = TextBlock::parse("12\n3x\n");
+        let error = text
+            .blocks()
+            .next()
+            .unwrap()
+            .transposed()
+            .nth(1)
+            .unwrap()
+            .parse::<u8>()
+            .unwrap_err();
+
+        assert_eq!(
+            error.message,
+            "invalid value `2x`: invalid digit found in string"
+        );
+        assert_eq!((error.line, error.column), (1, 2));
+    }
+
+    #[test]
+    fn handles_empty_text() {
+        let text = TextBlock::parse("\n  \n");
+        assert_eq!(text.height(), 0);
+        assert_eq!(text.blocks().count(), 0);
+    }
+}
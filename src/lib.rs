@@ -1,3 +1,11 @@
+pub mod bigint;
+pub mod dial;
+pub mod generate;
+pub mod grid;
+pub mod intervals;
+pub mod layout;
+pub mod parse;
+pub mod property;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
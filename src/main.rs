@@ -1,13 +1,15 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, generate, read, scaffold, solve, time, verify,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Calendar, Day};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{DEFAULT_THRESHOLD, Day, InputSource, Limits};
     use std::process;
 
     pub enum AppArguments {
@@ -27,14 +29,36 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            all_inputs: bool,
+            input: InputSource,
         },
         All {
             release: bool,
+            all_inputs: bool,
+            limits: Limits,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            limits: Limits,
+        },
+        Scaling {
+            day: Option<Day>,
+            sizes: Option<Vec<usize>>,
+            csv: Option<String>,
+            limits: Limits,
+        },
+        Verify {
+            day: Option<Day>,
+            all_inputs: bool,
+            limits: Limits,
+        },
+        Generate {
+            day: Day,
+            size: usize,
+            seed: u64,
         },
         #[cfg(feature = "today")]
         Today,
@@ -46,17 +70,40 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                all_inputs: args.contains("--all-inputs"),
+                limits: parse_limits(&mut args)?,
+            },
+            Some("time") if args.contains("--scaling") => AppArguments::Scaling {
+                sizes: args.opt_value_from_fn("--sizes", parse_sizes)?,
+                csv: args.opt_value_from_str("--csv")?,
+                limits: parse_limits(&mut args)?,
+                day: args.opt_free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let limits = parse_limits(&mut args)?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    compare: compare.then(|| threshold.unwrap_or(DEFAULT_THRESHOLD)),
+                    limits,
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                all_inputs: args.contains("--all-inputs"),
+                limits: parse_limits(&mut args)?,
+                day: args.opt_free_from_str()?,
+            },
+            Some("generate") => AppArguments::Generate {
+                day: args.free_from_str()?,
+                size: args.value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -73,6 +120,8 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                all_inputs: args.contains("--all-inputs"),
+                input: parse_input_source(&mut args)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +142,45 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_sizes(value: &str) -> Result<Vec<usize>, String> {
+        value
+            .split(',')
+            .map(|size| size.trim().parse::<usize>())
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("`--sizes` expects a comma-separated list of sizes: {e}"))
+    }
+
+    /// Limits of child solution runs: `--timeout <seconds>` per part, `0` to disable it, and
+    /// `--memory-limit <MiB>`.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        let timeout = args.opt_value_from_str("--timeout")?;
+        let memory = args.opt_value_from_str("--memory-limit")?;
+        Ok(Limits::from_args(timeout, memory)?)
+    }
+
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let sources: Vec<InputSource> = [
+            args.opt_value_from_str("--input")?.map(InputSource::Path),
+            args.opt_value_from_str("--example-part")?
+                .map(InputSource::ExamplePart),
+            args.contains("--example").then_some(InputSource::Example),
+            args.contains("-").then_some(InputSource::Stdin),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        match sources.as_slice() {
+            [] => Ok(InputSource::Default),
+            [source] => Ok(source.clone()),
+            _ => Err(
+                "only one of `--input`, `--example`, `--example-part` and `-` can be used.".into(),
+            ),
+        }
+    }
 }
 
 fn main() {
@@ -102,8 +190,30 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                all_inputs,
+                limits,
+            } => all::handle(release, all_inputs, limits),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                compare,
+                limits,
+            } => time::handle(day, all, store, compare, limits),
+            AppArguments::Scaling {
+                day,
+                sizes,
+                csv,
+                limits,
+            } => time::handle_scaling(day, sizes, csv, limits),
+            AppArguments::Verify {
+                day,
+                all_inputs,
+                limits,
+            } => verify::handle(day, all_inputs, limits),
+            AppArguments::Generate { day, size, seed } => generate::handle(day, size, seed),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -121,7 +231,9 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                all_inputs,
+                input,
+            } => solve::handle(day, release, dhat, submit, all_inputs, input),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -133,7 +245,8 @@ fn main() {
                     None => {
                         eprintln!(
                             "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            the {}th of december. Please use `scaffold` with a specific day.",
+                            Calendar::current().last_day()
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,319 @@
+//! Fallible parsing helpers that remember where in the input a piece of text came from.
+//! Errors carry the line, column and offending snippet, and render as a pointed diagnostic:
+//!
+//! ```text
+//! error: expected `L` or `R`
+//!  --> day 01, line 3, column 1
+//!   |
+//! 3 | X68
+//!   | ^
+//! ```
+use std::{error::Error, fmt::Display, ops::Range, str::FromStr};
+
+use crate::template::{ANSI_BOLD, ANSI_RED, ANSI_RESET, Day};
+
+/// An error in the puzzle input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Set by the runner, parsers do not need to know the day.
+    pub day: Option<Day>,
+    pub message: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The offending text, empty if the input ended unexpectedly.
+    pub snippet: String,
+    /// The full line the error occurred in.
+    pub source_line: String,
+}
+
+impl ParseError {
+    #[must_use]
+    pub fn with_day(mut self, day: Day) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Renders the error with the offending line and a marker below the snippet.
+    pub fn diagnostic(&self) -> String {
+        let location = match self.day {
+            Some(day) => format!("day {day}, line {}, column {}", self.line, self.column),
+            None => format!("line {}, column {}", self.line, self.column),
+        };
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        let marker = format!(
+            "{}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.snippet.chars().count().max(1))
+        );
+
+        [
+            format!(
+                "{ANSI_RED}{ANSI_BOLD}error{ANSI_RESET}{ANSI_BOLD}: {}{ANSI_RESET}",
+                self.message
+            ),
+            format!("{gutter}--> {location}"),
+            format!("{gutter} |"),
+            format!("{} | {}", self.line, self.source_line),
+            format!("{gutter} | {ANSI_RED}{marker}{ANSI_RESET}"),
+        ]
+        .join("\n")
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}: ")?;
+        }
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A piece of the input, along with the line it is part of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    line: usize,
+    source_line: &'a str,
+}
+
+impl<'a> Span<'a> {
+    /// A span covering a single line of text, e.g. for tests.
+    pub fn new(text: &'a str) -> Self {
+        Span {
+            text,
+            line: 1,
+            source_line: text,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// 1-based line number.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// 1-based column of the first character, counted in characters.
+    pub fn column(&self) -> usize {
+        self.source_line[..self.offset()].chars().count() + 1
+    }
+
+    /// Creates an error pointing at this span.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            day: None,
+            message: message.into(),
+            line: self.line,
+            column: self.column(),
+            snippet: self.text.to_string(),
+            source_line: self.source_line.to_string(),
+        }
+    }
+
+    /// Parses the span with [`FromStr`], e.g. into a number.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .parse()
+            .map_err(|e| self.error(format!("invalid value `{}`: {e}", self.text)))
+    }
+
+    /// Removes leading and trailing whitespace.
+    #[must_use]
+    pub fn trim(&self) -> Self {
+        let start = self.text.len() - self.text.trim_start().len();
+        self.sub(self.text[start..].trim_end())
+    }
+
+    /// Splits the span at a byte index.
+    pub fn split_at(&self, mid: usize) -> Result<(Self, Self), ParseError> {
+        if mid > self.text.len() || !self.text.is_char_boundary(mid) {
+            return Err(self.error(format!("expected at least {mid} characters")));
+        }
+        let (left, right) = self.text.split_at(mid);
+        Ok((self.sub(left), self.sub(right)))
+    }
+
+    /// A subslice of the span by byte range, [`None`] if the range is out of bounds or not on char boundaries.
+    pub fn get(&self, range: Range<usize>) -> Option<Self> {
+        self.text.get(range).map(|text| self.sub(text))
+    }
+
+    /// Splits the span at the first occurrence of `delimiter`.
+    pub fn split_once(&self, delimiter: &str) -> Result<(Self, Self), ParseError> {
+        let index = self
+            .text
+            .find(delimiter)
+            .ok_or_else(|| self.error(format!("expected `{delimiter}`")))?;
+        let left = &self.text[..index];
+        let right = &self.text[index + delimiter.len()..];
+        Ok((self.sub(left), self.sub(right)))
+    }
+
+    /// Splits the span at every occurrence of `delimiter`.
+    pub fn split(&self, delimiter: &'a str) -> impl Iterator<Item = Self> + 'a {
+        let span = *self;
+        self.text.split(delimiter).map(move |part| span.sub(part))
+    }
+
+    /// Splits the span into whitespace-separated fields.
+    pub fn fields(&self) -> impl Iterator<Item = Self> + 'a {
+        let span = *self;
+        self.text
+            .split_whitespace()
+            .map(move |field| span.sub(field))
+    }
+
+    /// Splits the span into single characters.
+    pub fn chars(&self) -> impl DoubleEndedIterator<Item = (char, Self)> + 'a {
+        let span = *self;
+        self.text
+            .char_indices()
+            .map(move |(i, c)| (c, span.sub(&span.text[i..i + c.len_utf8()])))
+    }
+
+    /// Creates a span for a subslice of this span's text.
+    fn sub(&self, text: &'a str) -> Self {
+        Span { text, ..*self }
+    }
+
+    fn offset(&self) -> usize {
+        self.text.as_ptr() as usize - self.source_line.as_ptr() as usize
+    }
+}
+
+/// Iterates the lines of an input as spans.
+pub fn lines(input: &str) -> Lines<'_> {
+    Lines {
+        input,
+        inner: input.lines().enumerate(),
+    }
+}
+
+/// Iterator over the lines of an input, see [`lines`].
+pub struct Lines<'a> {
+    input: &'a str,
+    inner: std::iter::Enumerate<std::str::Lines<'a>>,
+}
+
+impl<'a> Lines<'a> {
+    /// The next line, or an error if the input ended. `what` describes the expected line.
+    pub fn expect_line(&mut self, what: &str) -> Result<Span<'a>, ParseError> {
+        self.next()
+            .ok_or_else(|| end_of_input(self.input).error(format!("expected {what}")))
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Span<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(i, line)| Span {
+            text: line,
+            line: i + 1,
+            source_line: line,
+        })
+    }
+}
+
+/// An empty span at the very end of an input, to report truncated inputs.
+pub fn end_of_input(input: &str) -> Span<'_> {
+    let line = input.lines().count().max(1);
+    let source_line = input.lines().last().unwrap_or_default();
+    Span {
+        text: &source_line[source_line.len()..],
+        line,
+        source_line,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Span, end_of_input, lines};
+    use crate::day;
+
+    #[test]
+    fn tracks_locations() {
+        let input = "1-2\n 30 -4x\n";
+        let mut lines = lines(input);
+        lines.next();
+
+        let line = lines.next().unwrap();
+        assert_eq!(line.line(), 2);
+
+        let (left, right) = line.trim().split_once("-").unwrap();
+        assert_eq!(left.trim().parse::<u8>(), Ok(30));
+        assert_eq!(left.column(), 2);
+        assert_eq!(right.column(), 6);
+
+        let error = right.parse::<u8>().unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 6);
+        assert_eq!(error.snippet, "4x");
+        assert_eq!(error.source_line, " 30 -4x");
+    }
+
+    #[test]
+    fn splits_fields_and_chars() {
+        let span = Span::new("ab  c");
+        let fields: Vec<_> = span.fields().map(|f| (f.as_str(), f.column())).collect();
+        assert_eq!(fields, vec![("ab", 1), ("c", 5)]);
+
+        let (c, char_span) = span.chars().nth(4).unwrap();
+        assert_eq!(c, 'c');
+        assert_eq!(char_span.column(), 5);
+
+        assert!(span.split_at(10).is_err());
+        assert!(span.split_once(",").is_err());
+    }
+
+    #[test]
+    fn reports_truncated_input() {
+        let mut lines = lines("abc\n");
+        lines.next();
+        let error = lines.expect_line("a number").unwrap_err();
+        assert_eq!(error.message, "expected a number");
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(end_of_input("").column(), 1);
+    }
+
+    #[test]
+    fn renders_diagnostics() {
+        let error = Span::new("X68")
+            .split_at(1)
+            .unwrap()
+            .0
+            .error("expected `L` or `R`")
+            .with_day(day!(1));
+
+        assert_eq!(
+            error.to_string(),
+            "day 01: expected `L` or `R` at line 1, column 1"
+        );
+
+        let diagnostic = error.diagnostic();
+        assert!(diagnostic.contains("--> day 01, line 1, column 1"));
+        assert!(diagnostic.contains("1 | X68"));
+    }
+}
@@ -0,0 +1,321 @@
+//! Random puzzle inputs, printed in the format of the puzzle they belong to.
+use std::fmt::Display;
+
+use super::{Case, Rng, shrink_u64, shrink_vec};
+
+/// A comma-separated list of ID ranges on a single line, e.g. `11-22,95-115`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ranges {
+    /// Non-empty, every range has `start <= end`.
+    pub ranges: Vec<(u64, u64)>,
+}
+
+const MAX_RANGE_WIDTH: u64 = 500;
+
+impl Display for Ranges {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (start, end)) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{start}-{end}")?;
+        }
+        writeln!(f)
+    }
+}
+
+impl Case for Ranges {
+    fn generate(rng: &mut Rng, size: u32) -> Self {
+        let count = rng.index(1..=size.div_ceil(5) as usize);
+        let ranges = (0..count)
+            .map(|_| {
+                // spread ranges over numbers of all lengths, so that they cross powers of ten.
+                let digits = rng.range(1..=u64::from(size.min(12)));
+                let start = rng.range(0..=10u64.pow(digits as u32));
+                (start, start + rng.range(0..=MAX_RANGE_WIDTH))
+            })
+            .collect();
+
+        Ranges { ranges }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        shrink_vec(&self.ranges, |&(start, end)| {
+            let narrower = shrink_u64(end, start).into_iter().map(|end| (start, end));
+            let lower = shrink_u64(start, 0)
+                .into_iter()
+                .map(|s| (s, s + (end - start)));
+            narrower.chain(lower).collect()
+        })
+        .into_iter()
+        .filter(|ranges| !ranges.is_empty())
+        .map(|ranges| Ranges { ranges })
+        .collect()
+    }
+}
+
+/// Banks of batteries, one line of digits `1..=9` per bank.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Banks {
+    pub banks: Vec<Vec<u8>>,
+}
+
+impl Display for Banks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for bank in &self.banks {
+            bank.iter().try_for_each(|digit| write!(f, "{digit}"))?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Case for Banks {
+    fn generate(rng: &mut Rng, size: u32) -> Self {
+        let count = rng.index(1..=size.div_ceil(5) as usize);
+        let banks = (0..count)
+            .map(|_| {
+                let len = rng.index(2..=16);
+                (0..len).map(|_| rng.range(1..=9) as u8).collect()
+            })
+            .collect();
+
+        Banks { banks }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        shrink_vec(&self.banks, |bank| {
+            let shorter = shrink_vec(bank, |_| vec![])
+                .into_iter()
+                .filter(|bank| bank.len() >= 2);
+            let smaller = shrink_vec(bank, |&digit| {
+                shrink_u64(u64::from(digit), 1)
+                    .into_iter()
+                    .map(|digit| digit as u8)
+                    .collect()
+            })
+            .into_iter()
+            .filter(|smaller| smaller.len() == bank.len());
+            shorter.chain(smaller).collect()
+        })
+        .into_iter()
+        .filter(|banks| !banks.is_empty())
+        .map(|banks| Banks { banks })
+        .collect()
+    }
+}
+
+/// A grid of paper rolls `@` and empty spots `.`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RollGrid {
+    /// At least `1`.
+    pub width: usize,
+    /// Row by row, `true` for a roll. Never empty.
+    pub cells: Vec<bool>,
+}
+
+impl RollGrid {
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    fn without_row(&self, y: usize) -> Self {
+        let mut cells = self.cells.clone();
+        cells.drain(y * self.width..(y + 1) * self.width);
+        RollGrid {
+            width: self.width,
+            cells,
+        }
+    }
+
+    fn without_column(&self, x: usize) -> Self {
+        let cells = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % self.width != x)
+            .map(|(_, &cell)| cell)
+            .collect();
+        RollGrid {
+            width: self.width - 1,
+            cells,
+        }
+    }
+}
+
+impl Display for RollGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            for &roll in row {
+                write!(f, "{}", if roll { '@' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Case for RollGrid {
+    fn generate(rng: &mut Rng, size: u32) -> Self {
+        let width = rng.index(1..=size as usize);
+        let height = rng.index(1..=size as usize);
+        // denser grids have more rolls that only become accessible in later waves.
+        let density = rng.range(30..=90);
+        let cells = (0..width * height).map(|_| rng.chance(density)).collect();
+
+        RollGrid { width, cells }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut variants = vec![];
+
+        if self.height() > 1 {
+            variants.extend((0..self.height()).map(|y| self.without_row(y)));
+        }
+        if self.width > 1 {
+            variants.extend((0..self.width).map(|x| self.without_column(x)));
+        }
+
+        for (i, _) in self.cells.iter().enumerate().filter(|(_, roll)| **roll) {
+            let mut cells = self.cells.clone();
+            cells[i] = false;
+            variants.push(RollGrid {
+                width: self.width,
+                cells,
+            });
+        }
+
+        variants
+    }
+}
+
+/// Ranges of fresh ingredient IDs, a blank line, then the available ingredient IDs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inventory {
+    /// Non-empty, every range has `start <= end`.
+    pub ranges: Vec<(u64, u64)>,
+    pub ingredients: Vec<u64>,
+}
+
+const MAX_INGREDIENT: u64 = 200;
+
+impl Display for Inventory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (start, end) in &self.ranges {
+            writeln!(f, "{start}-{end}")?;
+        }
+        writeln!(f)?;
+        for ingredient in &self.ingredients {
+            writeln!(f, "{ingredient}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Case for Inventory {
+    fn generate(rng: &mut Rng, size: u32) -> Self {
+        // few, small IDs make overlapping and touching ranges likely.
+        let max = MAX_INGREDIENT.min(u64::from(size) * 4);
+        let ranges = (0..rng.index(1..=size as usize))
+            .map(|_| {
+                let start = rng.range(0..=max);
+                (start, rng.range(start..=max))
+            })
+            .collect();
+        let ingredients = (0..rng.index(0..=size as usize))
+            .map(|_| rng.range(0..=max))
+            .collect();
+
+        Inventory {
+            ranges,
+            ingredients,
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let fewer_ranges = shrink_vec(&self.ranges, |&(start, end)| {
+            let narrower = shrink_u64(end, start).into_iter().map(|end| (start, end));
+            let lower = shrink_u64(start, 0)
+                .into_iter()
+                .map(|s| (s, s + (end - start)));
+            narrower.chain(lower).collect()
+        })
+        .into_iter()
+        .filter(|ranges| !ranges.is_empty())
+        .map(|ranges| Inventory {
+            ranges,
+            ingredients: self.ingredients.clone(),
+        });
+
+        let fewer_ingredients = shrink_vec(&self.ingredients, |&id| shrink_u64(id, 0))
+            .into_iter()
+            .map(|ingredients| Inventory {
+                ranges: self.ranges.clone(),
+                ingredients,
+            });
+
+        fewer_ingredients.chain(fewer_ranges).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Banks, Inventory, Ranges, RollGrid};
+    use crate::property::{Case, Rng};
+
+    #[test]
+    fn prints_puzzle_inputs() {
+        let ranges = Ranges {
+            ranges: vec![(11, 22), (95, 115)],
+        };
+        assert_eq!(ranges.to_string(), "11-22,95-115\n");
+
+        let banks = Banks {
+            banks: vec![vec![9, 8, 1], vec![1, 2]],
+        };
+        assert_eq!(banks.to_string(), "981\n12\n");
+
+        let grid = RollGrid {
+            width: 3,
+            cells: vec![true, false, true, false, true, true],
+        };
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.to_string(), "@.@\n.@@\n");
+
+        let inventory = Inventory {
+            ranges: vec![(3, 5), (10, 14)],
+            ingredients: vec![1, 5],
+        };
+        assert_eq!(inventory.to_string(), "3-5\n10-14\n\n1\n5\n");
+    }
+
+    #[test]
+    fn keeps_cases_valid() {
+        let mut rng = Rng::new(1);
+        for size in 1..30 {
+            let ranges = Ranges::generate(&mut rng, size);
+            assert!(ranges.shrink().iter().chain([&ranges]).all(|case| {
+                !case.ranges.is_empty() && case.ranges.iter().all(|(start, end)| start <= end)
+            }));
+
+            let banks = Banks::generate(&mut rng, size);
+            assert!(banks.shrink().iter().chain([&banks]).all(|case| {
+                case.banks
+                    .iter()
+                    .all(|bank| bank.len() >= 2 && bank.iter().all(|d| (1..=9).contains(d)))
+            }));
+
+            let grid = RollGrid::generate(&mut rng, size);
+            assert!(grid.shrink().iter().chain([&grid]).all(|case| {
+                case.width > 0 && !case.cells.is_empty() && case.cells.len() % case.width == 0
+            }));
+
+            let inventory = Inventory::generate(&mut rng, size);
+            assert!(inventory.shrink().iter().chain([&inventory]).all(|case| {
+                !case.ranges.is_empty() && case.ranges.iter().all(|(start, end)| start <= end)
+            }));
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Property-based tests that compare an optimised solver against a brute-force reference on random inputs.
+//!
+//! A [`Case`] generates a random puzzle input of a given size and knows how to make itself smaller.
+//! [`Property::check`] runs a property against many cases. When a case fails, it is shrunk to a minimal
+//! failing input, which is written to `data/examples/{day}-counterexample.txt` to debug it like an example.
+//!
+//! ```ignore
+//! Property::new(DAY).check(|case: &Banks| {
+//!     let banks = parse(&case.to_string()).map_err(|e| e.to_string())?;
+//!     equal("part one", part_one(&banks), Some(brute_force(&case.banks, 2)))
+//! });
+//! ```
+use std::{
+    env,
+    fmt::{Debug, Display},
+    fs,
+    ops::RangeInclusive,
+};
+
+use crate::template::Day;
+
+pub mod inputs;
+
+/// Overrides the seed of all properties, e.g. to reproduce a failure.
+pub const SEED_ENV: &str = "AOC_PROPERTY_SEED";
+
+const DEFAULT_SEED: u64 = 0x00c0_ffee;
+const DEFAULT_CASES: u32 = 100;
+const DEFAULT_MAX_SIZE: u32 = 50;
+const MAX_SHRINK_STEPS: u32 = 1000;
+
+/// A small, deterministic pseudo-random number generator (`SplitMix64`).
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `range`. Slightly biased for very large ranges, which does not matter for tests.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = (*range.start(), *range.end());
+        assert!(start <= end, "empty range {start}..={end}");
+        match (end - start).checked_add(1) {
+            Some(len) => start + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// A `usize` in `range`.
+    pub fn index(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = (*range.start() as u64, *range.end() as u64);
+        usize::try_from(self.range(start..=end)).expect("range was given as usize")
+    }
+
+    /// `true` with a probability of `percent` percent.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.range(0..=99) < percent
+    }
+}
+
+/// A random puzzle input.
+pub trait Case: Clone + Debug + Display {
+    /// Generates a case, larger sizes should produce larger inputs.
+    fn generate(rng: &mut Rng, size: u32) -> Self;
+
+    /// Smaller variants of this case, most aggressive first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+/// Runs a property against random cases of a day.
+#[derive(Clone, Debug)]
+pub struct Property {
+    day: Day,
+    cases: u32,
+    max_size: u32,
+    seed: u64,
+}
+
+impl Property {
+    /// Creates a property with the default settings. The seed can be overridden with `AOC_PROPERTY_SEED`.
+    #[must_use]
+    pub fn new(day: Day) -> Self {
+        let seed = env::var(SEED_ENV)
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(DEFAULT_SEED);
+
+        Property {
+            day,
+            cases: DEFAULT_CASES,
+            max_size: DEFAULT_MAX_SIZE,
+            seed,
+        }
+    }
+
+    /// Number of cases to generate.
+    #[must_use]
+    pub fn cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Size of the last case, sizes grow linearly from `1`.
+    #[must_use]
+    pub fn max_size(mut self, max_size: u32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Checks `holds` for every generated case.
+    ///
+    /// # Panics
+    /// If a case fails, after shrinking it and writing it to `data/examples`.
+    #[track_caller]
+    pub fn check<C: Case>(&self, holds: impl Fn(&C) -> Result<(), String>) {
+        let mut rng = Rng::new(self.seed);
+
+        for i in 0..self.cases {
+            let size = 1 + i * self.max_size / self.cases.max(1);
+            let case = C::generate(&mut rng, size);
+
+            if let Err(message) = holds(&case) {
+                let (case, message, steps) = shrink(case, message, &holds);
+                let path = self.write_counterexample(&case);
+
+                panic!(
+                    "property failed for case {i} (seed {}, shrunk {steps} times): {message}\n\
+                     counterexample written to {path}:\n{case}",
+                    self.seed
+                );
+            }
+        }
+    }
+
+    fn write_counterexample(&self, case: &impl Display) -> String {
+        let path = get_counterexample_path(self.day);
+        if let Err(e) = fs::write(&path, case.to_string()) {
+            eprintln!("Could not write counterexample to {path}: {e}");
+        }
+        path
+    }
+}
+
+#[must_use]
+pub fn get_counterexample_path(day: Day) -> String {
+    format!("data/examples/{day}-counterexample.txt")
+}
+
+/// Repeatedly replaces the case with the first smaller variant that still fails.
+fn shrink<C: Case>(
+    mut case: C,
+    mut message: String,
+    holds: impl Fn(&C) -> Result<(), String>,
+) -> (C, String, u32) {
+    let mut steps = 0;
+
+    'shrink: while steps < MAX_SHRINK_STEPS {
+        for candidate in case.shrink() {
+            if let Err(candidate_message) = holds(&candidate) {
+                case = candidate;
+                message = candidate_message;
+                steps += 1;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+
+    (case, message, steps)
+}
+
+/// Compares a result with the reference result, for use in properties.
+pub fn equal<T: PartialEq + Debug>(what: &str, actual: T, expected: T) -> Result<(), String> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!("{what}: got {actual:?}, expected {expected:?}"))
+    }
+}
+
+/// Smaller variants of a list: without halves, without single items, then with single items shrunk.
+pub fn shrink_vec<T: Clone>(items: &[T], shrink_item: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut variants = vec![];
+
+    let half = items.len() / 2;
+    if half > 0 {
+        variants.push(items[half..].to_vec());
+        variants.push(items[..half].to_vec());
+    }
+
+    for i in 0..items.len() {
+        let mut variant = items.to_vec();
+        variant.remove(i);
+        variants.push(variant);
+    }
+
+    for (i, item) in items.iter().enumerate() {
+        for smaller in shrink_item(item) {
+            let mut variant = items.to_vec();
+            variant[i] = smaller;
+            variants.push(variant);
+        }
+    }
+
+    variants
+}
+
+/// Smaller values between `min` and `n`, closest to `min` first.
+pub fn shrink_u64(n: u64, min: u64) -> Vec<u64> {
+    if n <= min {
+        return vec![];
+    }
+
+    let mut values = vec![min, min + (n - min) / 2, n - 1];
+    values.retain(|&v| v >= min && v < n);
+    values.dedup();
+    values
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Case, Property, Rng, equal, shrink_u64, shrink_vec};
+    use crate::day;
+    use std::fmt::Display;
+
+    #[derive(Clone, Debug)]
+    struct Numbers(Vec<u64>);
+
+    impl Display for Numbers {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.iter().try_for_each(|n| writeln!(f, "{n}"))
+        }
+    }
+
+    impl Case for Numbers {
+        fn generate(rng: &mut Rng, size: u32) -> Self {
+            let len = rng.index(0..=size as usize);
+            Numbers((0..len).map(|_| rng.range(0..=1000)).collect())
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            shrink_vec(&self.0, |&n| shrink_u64(n, 0))
+                .into_iter()
+                .map(Numbers)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn generates_deterministically() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let values: Vec<u64> = (0..100).map(|_| a.range(3..=5)).collect();
+        assert!(values.iter().all(|v| (3..=5).contains(v)));
+        assert_eq!(values, (0..100).map(|_| b.range(3..=5)).collect::<Vec<_>>());
+        assert!(a.range(0..=u64::MAX) != a.range(0..=u64::MAX));
+    }
+
+    #[test]
+    fn passes_valid_properties() {
+        Property::new(day!(1)).check(|case: &Numbers| {
+            let sorted = {
+                let mut sorted = case.0.clone();
+                sorted.sort_unstable();
+                sorted
+            };
+            equal("len", sorted.len(), case.0.len())
+        });
+    }
+
+    #[test]
+    fn shrinks_failures() {
+        let holds = |case: &Numbers| {
+            if case.0.iter().any(|&n| n >= 500) {
+                Err("too large".to_string())
+            } else {
+                Ok(())
+            }
+        };
+
+        let case = Numbers(vec![3, 700, 12, 900, 5]);
+        let (case, message, _) = super::shrink(case, "too large".into(), holds);
+        assert_eq!(case.0, vec![500]);
+        assert_eq!(message, "too large");
+    }
+
+    #[test]
+    fn shrinks_numbers() {
+        assert_eq!(shrink_u64(10, 0), vec![0, 5, 9]);
+        assert_eq!(shrink_u64(2, 1), vec![1]);
+        assert!(shrink_u64(1, 1).is_empty());
+        assert_eq!(
+            shrink_vec(&[1, 2], |_| vec![]),
+            vec![vec![2], vec![1], vec![2], vec![1]]
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Converts Advent of Code puzzle pages to markdown.
+//! This only handles the small subset of HTML that puzzle descriptions are written in.
+
+/// Convert the `<article>` elements of a puzzle page to markdown.
+/// Falls back to converting the whole document if it contains no articles.
+pub fn to_markdown(html: &str) -> String {
+    let articles = extract_articles(html);
+    let sources = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+
+    let mut converter = Converter::default();
+    for source in sources {
+        converter.ensure_block();
+        converter.convert(source);
+    }
+
+    let mut markdown = converter.out.trim().to_string();
+    markdown.push('\n');
+    markdown
+}
+
+/// Returns the plain text content of the first `<article>` (or the document), with whitespace collapsed.
+pub fn article_text(html: &str) -> String {
+    let source = extract_articles(html).first().copied().unwrap_or(html);
+    let mut text = String::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&decode_entities(&rest[..start]));
+        text.push(' ');
+        rest = rest[start..]
+            .find('>')
+            .map_or("", |end| &rest[start + end + 1..]);
+    }
+    text.push_str(&decode_entities(rest));
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(close) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + close]);
+        rest = &rest[content_start + close + "</article>".len()..];
+    }
+
+    articles
+}
+
+#[derive(Default)]
+struct Converter {
+    out: String,
+    in_pre: usize,
+    in_code: usize,
+    links: Vec<String>,
+}
+
+impl Converter {
+    fn convert(&mut self, html: &str) {
+        let mut rest = html;
+
+        while let Some(start) = rest.find('<') {
+            self.text(&rest[..start]);
+            let Some(end) = rest[start..].find('>') else {
+                rest = "";
+                break;
+            };
+            self.tag(&rest[start + 1..start + end]);
+            rest = &rest[start + end + 1..];
+        }
+
+        self.text(rest);
+    }
+
+    fn text(&mut self, raw: &str) {
+        let text = decode_entities(raw);
+
+        if self.in_pre > 0 {
+            self.out.push_str(&text);
+            return;
+        }
+
+        for (i, word) in text.split_whitespace().enumerate() {
+            let at_line_start = self.out.is_empty() || self.out.ends_with('\n');
+            let starts_with_space = i > 0 || text.starts_with(char::is_whitespace);
+            if starts_with_space && !at_line_start && !self.out.ends_with(' ') {
+                self.out.push(' ');
+            }
+            self.out.push_str(word);
+        }
+
+        if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+            self.out.push(' ');
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let is_closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/');
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                self.ensure_block();
+                self.out.push_str("## ");
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p", true) => self.end_block(),
+            ("p" | "ul" | "ol", false) => self.ensure_block(),
+            ("ul" | "ol", true) => self.end_block(),
+            ("li", false) => {
+                self.ensure_line();
+                self.out.push_str("- ");
+            }
+            ("li", true) => self.ensure_line(),
+            ("pre", false) => {
+                self.ensure_block();
+                self.out.push_str("```\n");
+                self.in_pre += 1;
+            }
+            ("pre", true) => {
+                self.in_pre = self.in_pre.saturating_sub(1);
+                self.ensure_line();
+                self.out.push_str("```");
+                self.end_block();
+            }
+            ("code", false) => {
+                if self.in_pre == 0 {
+                    self.out.push('`');
+                }
+                self.in_code += 1;
+            }
+            ("code", true) => {
+                self.in_code = self.in_code.saturating_sub(1);
+                if self.in_pre == 0 {
+                    self.out.push('`');
+                }
+            }
+            ("em" | "strong" | "b" | "i", _) if self.in_pre == 0 && self.in_code == 0 => {
+                self.out.push('*');
+            }
+            ("a", false) => {
+                self.links.push(attribute(tag, "href").unwrap_or_default());
+                self.out.push('[');
+            }
+            ("a", true) => {
+                let href = self.links.pop().unwrap_or_default();
+                self.out.push_str(&format!("]({href})"));
+            }
+            ("br", _) => self.out.push('\n'),
+            _ => {}
+        }
+    }
+
+    fn trim_trailing_spaces(&mut self) {
+        while self.out.ends_with(' ') {
+            self.out.pop();
+        }
+    }
+
+    fn ensure_line(&mut self) {
+        self.trim_trailing_spaces();
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn ensure_block(&mut self) {
+        self.ensure_line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn end_block(&mut self) {
+        self.ensure_line();
+        self.out.push('\n');
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{article_text, decode_entities, to_markdown};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp; &#39;c&#x27; &foo"),
+            "a <b> & 'c' &foo"
+        );
+    }
+
+    #[test]
+    fn converts_puzzle_articles() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Secret Entrance ---</h2><p>The Elves have good news
+and <em>bad</em> news.</p>
+<p>For example, see <a href="/2025/day/1/input" target="_blank">input</a>:</p>
+<pre><code>L68
+L30 &lt; R48
+</code></pre>
+<ul>
+<li>The dial starts at <code>50</code>.</li>
+<li>It ends at <code><em>0</em></code>.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>More.</p></article>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Secret Entrance ---",
+            "",
+            "The Elves have good news and *bad* news.",
+            "",
+            "For example, see [input](/2025/day/1/input):",
+            "",
+            "```",
+            "L68",
+            "L30 < R48",
+            "```",
+            "",
+            "- The dial starts at `50`.",
+            "- It ends at `0`.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "More.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(to_markdown(html), expected);
+    }
+
+    #[test]
+    fn converts_documents_without_articles() {
+        assert_eq!(
+            to_markdown("<p>Hello <em>world</em>!</p>"),
+            "Hello *world*!\n"
+        );
+    }
+
+    #[test]
+    fn extracts_article_text() {
+        let html = "<main><article><p>That's the <em>right</em>\n answer!</p></article></main>";
+        assert_eq!(article_text(html), "That's the right answer!");
+    }
+}
@@ -0,0 +1,456 @@
+/// Minimal client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers on behalf of the user's session.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::template::{Calendar, Day};
+
+mod markdown;
+
+pub use markdown::to_markdown;
+
+/// Environment variable that overrides the session cookie file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Environment variable that overrides the website's base URL, e.g. to point the client at a mock server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str =
+    "advent-of-code-rust template (https://github.com/fspoettel/advent-of-code-rust)";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    MissingYear,
+    Http(u16, String),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `{SESSION_ENV}` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::MissingYear => {
+                write!(
+                    f,
+                    "no event year configured. Set `AOC_YEAR` in `.cargo/config.toml`."
+                )
+            }
+            AocClientError::Http(status, body) => {
+                write!(f, "server responded with status {status}: {}", body.trim())
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                AocClientError::Http(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+    /// The part was already solved, or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Classify the text of a submission response.
+    pub fn from_response_text(text: &str) -> Self {
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else if text.contains("solving the right level") {
+            Verdict::WrongLevel
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Verdict::TooHigh
+            } else if text.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else {
+            Verdict::Unknown
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            "wrong_level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// The response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub verdict: Verdict,
+    /// The server's message as plain text.
+    pub message: String,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Create a client from the environment: base URL from `AOC_BASE_URL`, year from `AOC_YEAR`,
+    /// session from `AOC_SESSION` or the `~/.adventofcode.session` file.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let year = get_year().ok_or(AocClientError::MissingYear)?;
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Fetch the personal puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}/input", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetch the puzzle page of a day as HTML.
+    pub fn puzzle_html(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(day))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetch the puzzle description of a day as markdown.
+    pub fn puzzle_markdown(&self, day: Day) -> Result<String, AocClientError> {
+        self.puzzle_html(day).map(|html| to_markdown(&html))
+    }
+
+    /// Submit an answer for a part of a day.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Submission, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let message = markdown::article_text(&response.into_string()?);
+
+        Ok(Submission {
+            verdict: Verdict::from_response_text(&message),
+            message,
+        })
+    }
+}
+
+fn get_year() -> Option<u16> {
+    match env::var("AOC_YEAR") {
+        Ok(x) => x.parse().ok(),
+        Err(_) => Calendar::current().year(),
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV)
+        && !session.trim().is_empty()
+    {
+        return Some(session.trim().to_string());
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()?;
+    let session = session.trim();
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+pub fn get_input_path(day: Day) -> String {
+    format!("data/inputs/{day}.txt")
+}
+
+pub fn get_puzzle_path(day: Day) -> String {
+    format!("data/puzzles/{day}.md")
+}
+
+/// Download the input and puzzle description of a day to the `data` directory.
+pub fn download(day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    write_file(&input_path, &client.input(day)?)?;
+    write_file(&puzzle_path, &client.puzzle_markdown(day)?)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetch the puzzle description of a day, store it and print it to the terminal.
+pub fn read(day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let markdown = client.puzzle_markdown(day)?;
+    write_file(&get_puzzle_path(day), &markdown)?;
+    println!("{markdown}");
+    Ok(())
+}
+
+/// Writes a file below `data`, creating its directory first in a fresh checkout.
+fn write_file(path: &str, contents: &str) -> io::Result<()> {
+    let path = Path::new(path);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Submit an answer for a part of a day.
+pub fn submit(day: Day, part: u8, answer: &str) -> Result<Submission, AocClientError> {
+    AocClient::from_env()?.submit(day, part, answer)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError, Verdict, write_file};
+    use crate::day;
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        process, thread,
+    };
+
+    /// Serve one canned response per expected request and return the raw requests.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut request = String::new();
+                    let mut content_length = 0;
+
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                            content_length = len.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" || line.is_empty() {
+                            break;
+                        }
+                    }
+
+                    let mut body_buf = vec![0; content_length];
+                    reader.read_exact(&mut body_buf).unwrap();
+                    request.push_str(&String::from_utf8(body_buf).unwrap());
+
+                    let mut stream = reader.into_inner();
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_inputs_and_puzzles() {
+        let (base_url, server) = mock_server(vec![
+            (200, "L68\nR48\n"),
+            (
+                200,
+                "<main><article><h2>--- Day 1 ---</h2><p>Hi</p></article></main>",
+            ),
+        ]);
+        let client = AocClient::new(&base_url, "secret\n", 2025);
+
+        assert_eq!(client.input(day!(1)).unwrap(), "L68\nR48\n");
+        assert_eq!(
+            client.puzzle_markdown(day!(1)).unwrap(),
+            "## --- Day 1 ---\n\nHi\n"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("Cookie: session=secret\r\n"));
+        assert!(requests[1].starts_with("GET /2025/day/1 HTTP/1.1"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = mock_server(vec![(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        )]);
+        let client = AocClient::new(&base_url, "secret", 2025);
+
+        let submission = client.submit(day!(3), 2, "42").unwrap();
+        assert_eq!(submission.verdict, Verdict::TooHigh);
+        assert_eq!(
+            submission.message,
+            "That's not the right answer; your answer is too high."
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2025/day/3/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let (base_url, server) = mock_server(vec![(400, "Please log in.")]);
+        let client = AocClient::new(&base_url, "expired", 2025);
+
+        match client.input(day!(1)) {
+            Err(AocClientError::Http(400, body)) => assert_eq!(body, "Please log in."),
+            other => panic!("unexpected result: {other:?}"),
+        }
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn classifies_verdicts() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                Verdict::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have 30s left to wait.",
+                Verdict::RateLimited,
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                Verdict::WrongLevel,
+            ),
+            ("Something else", Verdict::Unknown),
+        ];
+
+        for (text, verdict) in cases {
+            assert_eq!(Verdict::from_response_text(text), verdict);
+        }
+    }
+
+    #[test]
+    fn creates_missing_directories() {
+        let dir = env::temp_dir().join(format!("aoc-download-{}", process::id()));
+        let path = dir.join("data/inputs/01.txt");
+
+        write_file(path.to_str().unwrap(), "input").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "input");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,5 +1,18 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{Limits, all_days, run_multi::run_multi};
+
+pub fn handle(is_release: bool, all_inputs: bool, limits: Limits) {
+    let summary = run_multi(
+        &all_days().collect(),
+        is_release,
+        false,
+        all_inputs,
+        &limits,
+    );
+
+    let exit_code = summary.exit_code();
+    if exit_code != 0 {
+        process::exit(exit_code);
+    }
 }
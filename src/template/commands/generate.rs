@@ -0,0 +1,36 @@
+use std::{fs, path::Path, process};
+
+use crate::generate::{generator, get_generated_path};
+use crate::property::Rng;
+use crate::template::Day;
+
+pub fn handle(day: Day, size: usize, seed: u64) {
+    let Some(generator) = generator(day) else {
+        eprintln!("Day {day} has no input generator.");
+        process::exit(1);
+    };
+
+    let path = get_generated_path(day, size);
+    if let Some(dir) = Path::new(&path).parent()
+        && let Err(e) = fs::create_dir_all(dir)
+    {
+        eprintln!("Failed to create directory \"{}\": {e}", dir.display());
+        process::exit(1);
+    }
+
+    let input = generator.generate(&mut Rng::new(seed), size);
+    match fs::write(&path, input) {
+        Ok(()) => {
+            println!(
+                "Generated input with {size} {} (seed {seed}) in \"{path}\"",
+                generator.unit()
+            );
+            println!("---");
+            println!("🎄 Type `cargo solve {day} --input {path}` to run your solution on it.");
+        }
+        Err(e) => {
+            eprintln!("Failed to write generated input: {e}");
+            process::exit(1);
+        }
+    }
+}
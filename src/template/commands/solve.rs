@@ -1,8 +1,16 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::run_multi::{get_inputs_to_run, print_input_label};
+use crate::template::{Day, InputSource};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    all_inputs: bool,
+    source: InputSource,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -16,6 +24,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if !dhat && cfg!(feature = "heap-stats") {
+        cmd_args.extend(["--features".to_string(), "heap-stats".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,12 +35,26 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    // an explicitly selected input source replaces the set of inputs.
+    let sources = if source == InputSource::Default {
+        get_inputs_to_run(day, all_inputs)
+    } else {
+        vec![source]
+    };
+
+    for source in &sources {
+        print_input_label(day, source, sources.len());
 
-    cmd.wait().unwrap();
+        let mut cmd_args = cmd_args.clone();
+        cmd_args.extend(source.to_args());
+
+        let mut cmd = Command::new("cargo")
+            .args(&cmd_args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .unwrap();
+
+        cmd.wait().unwrap();
+    }
 }
@@ -1,16 +1,25 @@
 use std::collections::HashSet;
+use std::path::Path;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::generate::generator;
+use crate::template::run_multi::{get_path_for_bin, run_multi};
+use crate::template::scaling::{self, DEFAULT_SIZES, Scaling};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, Limits, all_days, comparison, heap, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(day: Option<Day>, run_all: bool, store: bool, compare: Option<f64>, limits: Limits) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare.is_some() {
+                // when comparing, bench every day that has a baseline.
+                all_days()
+                    .filter(|day| stored_timings.get(*day).is_some())
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,7 +30,19 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let summary = run_multi(&days_to_run, true, true, false, &limits);
+    let timings = summary.timings.clone().unwrap();
+
+    if !heap::is_enabled() {
+        println!(
+            "\nHeap usage was not tracked, bench with `cargo run --release --features heap-stats -- time` to include it."
+        );
+    }
+
+    let has_regression = compare.is_some_and(|threshold| {
+        println!();
+        comparison::print(&comparison::compare(&stored_timings, &timings), threshold)
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -37,4 +58,66 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regression {
+        eprintln!("\nAt least one part regressed beyond the threshold.");
+        process::exit(1);
+    }
+
+    // unsolved parts are expected while benching, only failures change the exit code.
+    if !summary.failures.is_empty() {
+        eprintln!("\nAt least one part panicked or was aborted.");
+        process::exit(1);
+    }
+}
+
+/// Benches days across generated inputs of growing size. Without a day, every solved day with an input
+/// generator is benched.
+pub fn handle_scaling(
+    day: Option<Day>,
+    sizes: Option<Vec<usize>>,
+    csv: Option<String>,
+    limits: Limits,
+) {
+    let sizes = sizes.unwrap_or_else(|| DEFAULT_SIZES.to_vec());
+
+    let days_to_run: Vec<Day> = day.map_or_else(
+        || {
+            all_days()
+                .filter(|day| {
+                    generator(*day).is_some() && Path::new(&get_path_for_bin(*day)).exists()
+                })
+                .collect()
+        },
+        |day| vec![day],
+    );
+
+    let mut scalings: Vec<Scaling> = vec![];
+    for day in days_to_run {
+        match scaling::run(day, &sizes, &limits) {
+            Ok(scaling) => scalings.push(scaling),
+            Err(e) => {
+                eprintln!("Could not bench the scaling of day {day}: {e}");
+                process::exit(1);
+            }
+        }
+        println!();
+    }
+
+    for (i, scaling) in scalings.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        scaling.print();
+    }
+
+    if let Some(path) = csv {
+        match scaling::store_csv(&path, &scalings) {
+            Ok(()) => println!("\nStored scaling timings in \"{path}\"."),
+            Err(e) => {
+                eprintln!("Failed to store scaling timings: {e}");
+                process::exit(1);
+            }
+        }
+    }
 }
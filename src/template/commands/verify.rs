@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::process;
+
+use crate::template::aoc_client::get_input_path;
+use crate::template::input::named_inputs;
+use crate::template::ledger::Ledger;
+use crate::template::run_multi::{
+    child_commands, get_inputs_to_run, get_path_for_bin, print_input_label,
+};
+use crate::template::verify::{self, Answers, Outcome};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, InputSource, Limits, all_days};
+
+pub fn handle(day: Option<Day>, all_inputs: bool, limits: Limits) {
+    let ledger = Ledger::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Could not read answer ledger, ignoring it: {e}");
+        Ledger::default()
+    });
+
+    // without a day, verify every day that has a solution and at least one input.
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || {
+            all_days()
+                .filter(|day| {
+                    Path::new(&get_path_for_bin(*day)).exists()
+                        && (Path::new(&get_input_path(*day)).exists()
+                            || (all_inputs && !named_inputs(*day).is_empty()))
+                })
+                .collect()
+        },
+        |day| HashSet::from([day]),
+    );
+
+    let mut verifications = vec![];
+    let mut failed_runs: Vec<(Day, Option<String>)> = vec![];
+    let mut need_space = false;
+
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let inputs = get_inputs_to_run(day, all_inputs);
+
+        for input in &inputs {
+            print_input_label(day, input, inputs.len());
+
+            let answers = match Answers::read(day, input.name()) {
+                // the ledger only knows the answers of the default input.
+                Ok(answers) if *input == InputSource::Default => answers.or_ledger(&ledger, day),
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("Could not read expected answers of day {day}: {e}");
+                    process::exit(1);
+                }
+            };
+
+            let reports = match child_commands::run_solution(day, false, true, input, &limits) {
+                Ok(reports) => reports,
+                Err(e) => {
+                    eprintln!("Could not run day {day}: {e}");
+                    failed_runs.push((day, input.name().map(ToString::to_string)));
+                    continue;
+                }
+            };
+            let input_verifications = verify::verify(day, input.name(), &reports, &answers);
+
+            println!();
+            verify::print(&input_verifications);
+            verifications.extend(input_verifications);
+        }
+    }
+
+    let count = |f: fn(&Outcome) -> bool| verifications.iter().filter(|v| f(&v.outcome)).count();
+    let passed = count(|x| matches!(x, Outcome::Pass));
+    let failed = count(|x| matches!(x, Outcome::Fail { .. }));
+    let missing = count(|x| matches!(x, Outcome::Missing { .. }));
+
+    // runs that could not be started count as failures, their parts were never checked.
+    let failed = failed + failed_runs.len();
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {passed} passed, {failed} failed, {missing} missing"
+    );
+
+    if failed > 0 {
+        failed_runs.iter().for_each(|(day, input)| match input {
+            Some(name) => eprintln!("Failed to run: Day {day} (input `{name}`)"),
+            None => eprintln!("Failed to run: Day {day}"),
+        });
+        verifications
+            .iter()
+            .filter(|v| matches!(v.outcome, Outcome::Fail { .. }))
+            .for_each(|v| match &v.input {
+                Some(name) => eprintln!("Failed: Day {} Part {} (input `{name}`)", v.day, v.part),
+                None => eprintln!("Failed: Day {} Part {}", v.day, v.part),
+            });
+        process::exit(1);
+    }
+}
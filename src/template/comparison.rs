@@ -0,0 +1,156 @@
+/// Compares fresh benchmark results against a stored baseline.
+use crate::template::stats::format_nanos;
+use crate::template::timings::Timings;
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, Day};
+
+/// Default regression threshold in percent.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Change of a single part compared to its baseline, part `0` is the parse step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: Option<f64>,
+    pub current_nanos: f64,
+}
+
+impl PartComparison {
+    /// Relative change in percent, positive values are slower than the baseline.
+    pub fn change_percent(&self) -> Option<f64> {
+        self.baseline_nanos
+            .filter(|baseline| *baseline > 0.0)
+            .map(|baseline| (self.current_nanos - baseline) / baseline * 100.0)
+    }
+
+    /// Returns `true` if the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent().is_some_and(|x| x > threshold)
+    }
+
+    /// Returns `true` if the part got faster by more than `threshold` percent.
+    pub fn is_improvement(&self, threshold: f64) -> bool {
+        self.change_percent().is_some_and(|x| x < -threshold)
+    }
+}
+
+/// Compare every benched part in `current` against `baseline`.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartComparison> {
+    current
+        .data
+        .iter()
+        .flat_map(|timing| {
+            [0, 1, 2].into_iter().filter_map(|part| {
+                Some(PartComparison {
+                    day: timing.day,
+                    part,
+                    baseline_nanos: baseline
+                        .get(timing.day)
+                        .and_then(|base| base.part_nanos(part)),
+                    current_nanos: timing.part_nanos(part)?,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Print a comparison table, returns `true` if any part regressed beyond `threshold`.
+pub fn print(comparisons: &[PartComparison], threshold: f64) -> bool {
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET} (threshold: ±{threshold:.1}%)");
+    println!("----------");
+
+    if comparisons.is_empty() {
+        println!("No benched parts to compare.");
+        return false;
+    }
+
+    for comparison in comparisons {
+        let label = match comparison.part {
+            0 => format!("Day {} Parse", comparison.day),
+            part => format!("Day {} Part {part}", comparison.day),
+        };
+        let current = format_nanos(comparison.current_nanos);
+
+        match (comparison.baseline_nanos, comparison.change_percent()) {
+            (Some(baseline), Some(change)) => {
+                let baseline = format_nanos(baseline);
+                let marker = if comparison.is_regression(threshold) {
+                    format!("{ANSI_RED}▲ {change:+.1}% regression{ANSI_RESET}")
+                } else if comparison.is_improvement(threshold) {
+                    format!("{ANSI_GREEN}▼ {change:+.1}% improvement{ANSI_RESET}")
+                } else {
+                    format!("≈ {change:+.1}%")
+                };
+                println!("{label}: {baseline} → {current} {marker}");
+            }
+            _ => println!("{label}: {current} (no baseline)"),
+        }
+    }
+
+    comparisons.iter().any(|c| c.is_regression(threshold))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartComparison, compare};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn timings(day: u8, part_1: Option<&str>, part_2: Option<&str>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                part_1: part_1.map(PartTiming::new),
+                part_2: part_2.map(PartTiming::new),
+                ..Timing::new(crate::template::Day::new(day).unwrap())
+            }],
+        }
+    }
+
+    #[test]
+    fn compares_parts() {
+        let baseline = timings(1, Some("100.0ns"), Some("1.0ms"));
+        let current = timings(1, Some("120.0ns"), Some("500.0µs"));
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(comparisons.len(), 2);
+        assert!((comparisons[0].change_percent().unwrap() - 20.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(25.0));
+        assert!((comparisons[1].change_percent().unwrap() + 50.0).abs() < 1e-9);
+        assert!(comparisons[1].is_improvement(10.0));
+    }
+
+    #[test]
+    fn handles_missing_baseline() {
+        let baseline = timings(2, Some("100.0ns"), None);
+        let current = timings(1, Some("120.0ns"), None);
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(
+            comparisons,
+            vec![PartComparison {
+                day: day!(1),
+                part: 1,
+                baseline_nanos: None,
+                current_nanos: 120.0,
+            }]
+        );
+        assert_eq!(comparisons[0].change_percent(), None);
+        assert!(!comparisons[0].is_regression(0.0));
+    }
+
+    #[test]
+    fn skips_unsolved_parts() {
+        let baseline = timings(1, Some("100.0ns"), Some("100.0ns"));
+        let current = timings(1, None, Some("100.0ns"));
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, 2);
+        assert_eq!(comparisons[0].change_percent(), Some(0.0));
+    }
+}
@@ -8,7 +8,91 @@ use chrono::{Datelike, FixedOffset, Utc};
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// The first event year that only has 12 puzzles.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// The advent calendar of an event year.
+///
+/// Events up to 2024 have 25 puzzles, events from 2025 onwards have 12.
+/// When no year is known, the calendar falls back to 25 days.
+///
+/// ```
+/// # use advent_of_code::template::Calendar;
+/// assert_eq!(Calendar::new(2024).last_day(), 25);
+/// assert_eq!(Calendar::new(2025).last_day(), 12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calendar {
+    year: Option<u16>,
+}
+
+impl Calendar {
+    /// Creates the [`Calendar`] of the given event year.
+    pub const fn new(year: u16) -> Self {
+        Self { year: Some(year) }
+    }
+
+    /// Returns the calendar of the year configured via `AOC_YEAR` at compile time.
+    pub const fn current() -> Self {
+        Self {
+            year: parse_year(option_env!("AOC_YEAR")),
+        }
+    }
+
+    /// The configured event year, if any.
+    pub const fn year(self) -> Option<u16> {
+        self.year
+    }
+
+    /// The number of the last puzzle of the event.
+    pub const fn last_day(self) -> u8 {
+        match self.year {
+            Some(year) if year >= FIRST_SHORT_YEAR => 12,
+            _ => 25,
+        }
+    }
+
+    /// Returns `true` if the event has a puzzle for the given day number.
+    pub const fn contains(self, day: u8) -> bool {
+        day != 0 && day <= self.last_day()
+    }
+
+    /// An iterator that yields every day of this calendar.
+    pub fn days(self) -> AllDays {
+        AllDays {
+            current: 1,
+            last: self.last_day(),
+        }
+    }
+}
+
+/// Parses a year from an optional string in a const context.
+const fn parse_year(value: Option<&str>) -> Option<u16> {
+    let Some(value) = value else {
+        return None;
+    };
+
+    let bytes = value.as_bytes();
+    if bytes.is_empty() || bytes.len() > 4 {
+        return None;
+    }
+
+    let mut year: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        year = year * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    Some(year)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A valid day number of advent (i.e. an integer in range 1 to the last day of the configured year).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -22,10 +106,16 @@ const SERVER_UTC_OFFSET: i32 = -5;
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
-    /// returns [`None`] otherwise.
+    /// Creates a [`Day`] from the provided value if it's in the valid range of the
+    /// configured year's [`Calendar`], returns [`None`] otherwise.
     pub const fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        Self::new_in(day, Calendar::current())
+    }
+
+    /// Creates a [`Day`] from the provided value if it's in the valid range of `calendar`,
+    /// returns [`None`] otherwise.
+    pub const fn new_in(day: u8, calendar: Calendar) -> Option<Self> {
+        if !calendar.contains(day) {
             return None;
         }
         Some(Self(day))
@@ -39,11 +129,12 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st of december and the last day of the
+    /// configured year's event, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -88,26 +179,31 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(
+            f,
+            "expecting a day number between 1 and {}",
+            Calendar::current().last_day()
+        )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent of the configured year.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of a [`Calendar`].
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Calendar::current().days()
     }
 }
 
@@ -115,10 +211,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -134,7 +230,7 @@ macro_rules! day {
     ($day:expr) => {
         const {
             $crate::template::Day::new($day)
-                .expect("invalid day number, expecting a value between 1 and 25")
+                .expect("invalid day number for the configured AOC_YEAR")
         }
     };
 }
@@ -143,11 +239,11 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{Calendar, Day, all_days};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = Calendar::new(2024).days();
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -176,6 +272,39 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_short_calendar() {
+        let days: Vec<Day> = Calendar::new(2025).days().collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.first(), Some(&Day(1)));
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
+
+    #[test]
+    fn all_days_follows_configured_year() {
+        assert_eq!(
+            all_days().count(),
+            usize::from(Calendar::current().last_day())
+        );
+    }
+
+    #[test]
+    fn validates_days_against_calendar() {
+        assert_eq!(Day::new_in(0, Calendar::new(2024)), None);
+        assert_eq!(Day::new_in(25, Calendar::new(2024)), Some(Day(25)));
+        assert_eq!(Day::new_in(26, Calendar::new(2024)), None);
+        assert_eq!(Day::new_in(12, Calendar::new(2025)), Some(Day(12)));
+        assert_eq!(Day::new_in(13, Calendar::new(2025)), None);
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!(super::parse_year(Some("2025")), Some(2025));
+        assert_eq!(super::parse_year(Some("20x5")), None);
+        assert_eq!(super::parse_year(Some("")), None);
+        assert_eq!(super::parse_year(None), None);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
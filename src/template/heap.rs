@@ -0,0 +1,272 @@
+//! Lightweight heap usage tracking for every run, enabled by the opt-in `heap-stats` feature.
+//!
+//! [`solution!`](crate::solution) installs [`CountingAlloc`] as the global allocator, which counts
+//! allocations per thread. The runner [`measure`]s the first call of the parse step and every part,
+//! allocations of threads spawned by a solution are not attributed to it.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    collections::HashMap,
+};
+use tinyjson::JsonValue;
+
+/// Heap usage of a single call.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Most bytes in use at once, on top of what was in use before the call.
+    pub peak_bytes: u64,
+    /// Bytes allocated in total, including reallocations.
+    pub total_bytes: u64,
+    pub allocations: u64,
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    current: u64,
+    peak: u64,
+    total: u64,
+    allocations: u64,
+}
+
+thread_local! {
+    // const-initialized without a destructor, so that the allocator never allocates to access it.
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { current: 0, peak: 0, total: 0, allocations: 0 })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        c.current = (c.current + allocated as u64).saturating_sub(freed as u64);
+        c.peak = c.peak.max(c.current);
+        if allocated > 0 {
+            c.total += allocated as u64;
+            c.allocations += 1;
+        }
+        counters.set(c);
+    });
+}
+
+/// A global allocator that forwards to the system allocator and counts allocations per thread.
+pub struct CountingAlloc;
+
+// SAFETY: all calls are forwarded to `System` unchanged.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Returns `true` if solutions are built with [`CountingAlloc`] as their global allocator.
+/// DHAT brings its own allocator, so heap stats are not tracked while profiling with it.
+pub const fn is_enabled() -> bool {
+    cfg!(all(feature = "heap-stats", not(feature = "dhat-heap")))
+}
+
+/// Runs `func` and returns the heap usage of the current thread during the call.
+/// Without [`CountingAlloc`] installed, the stats are [`None`].
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    let before = COUNTERS.with(|counters| {
+        let c = counters.get();
+        counters.set(Counters {
+            peak: c.current,
+            ..c
+        });
+        c
+    });
+
+    let result = func();
+    let after = COUNTERS.with(|counters| {
+        let c = counters.get();
+        // an enclosing call keeps its own peak, which includes the peak of this call.
+        counters.set(Counters {
+            peak: c.peak.max(before.peak),
+            ..c
+        });
+        c
+    });
+
+    let stats = HeapStats {
+        peak_bytes: after.peak.saturating_sub(before.current),
+        total_bytes: after.total - before.total,
+        allocations: after.allocations - before.allocations,
+    };
+
+    (result, is_enabled().then_some(stats))
+}
+
+/// Formats a number of bytes with binary prefixes, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl HeapStats {
+    /// A one-line summary, e.g. `peak 1.5 KiB · total 3.0 KiB · 12 allocations`.
+    pub fn summary(&self) -> String {
+        format!(
+            "peak {} · total {} · {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapStats> for JsonValue {
+    fn from(value: &HeapStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(value.allocations as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+                .ok_or(format!("Expected heap.{key} to be a number."))
+        };
+
+        Ok(HeapStats {
+            peak_bytes: number("peak_bytes")?,
+            total_bytes: number("total_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{HeapStats, format_bytes, measure};
+    use std::hint::black_box;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn measures_allocations() {
+        let (sum, _) = measure(|| black_box(vec![1u8; 64]).len());
+        assert_eq!(sum, 64);
+
+        // the allocator is only installed in the test build of the library itself.
+        if cfg!(test) && super::is_enabled() {
+            let (_, stats) = measure(|| {
+                let mut v: Vec<u64> = Vec::with_capacity(128);
+                v.extend(0..128);
+                drop(black_box(v));
+                black_box(vec![0u8; 100]).len()
+            });
+            let stats = stats.unwrap();
+            assert_eq!(stats.allocations, 2);
+            assert_eq!(stats.total_bytes, 1024 + 100);
+            assert_eq!(stats.peak_bytes, 1024);
+
+            // a nested call does not reset the peak of the enclosing one.
+            let (_, outer) = measure(|| {
+                let big = black_box(vec![0u8; 4096]);
+                drop(big);
+                let small = black_box(vec![0u8; 16]);
+                let (_, inner) = measure(|| black_box(vec![0u8; 64]).len());
+                assert_eq!(inner.unwrap().peak_bytes, 64);
+                small.len()
+            });
+            assert_eq!(outer.unwrap().peak_bytes, 4096);
+        }
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(
+            HeapStats {
+                peak_bytes: 2048,
+                total_bytes: 4096,
+                allocations: 3
+            }
+            .summary(),
+            "peak 2.0 KiB · total 4.0 KiB · 3 allocations"
+        );
+    }
+
+    #[test]
+    fn roundtrips_heap_stats() {
+        let stats = HeapStats {
+            peak_bytes: 10,
+            total_bytes: 20,
+            allocations: 2,
+        };
+        assert_eq!(HeapStats::try_from(&JsonValue::from(&stats)), Ok(stats));
+    }
+}
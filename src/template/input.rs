@@ -0,0 +1,273 @@
+//! Selects where a solution reads its puzzle input from.
+//! By default, the input is read from `data/inputs/{day}.txt`. Solution binaries and `cargo solve` accept:
+//!  - `--input <path>`: an arbitrary input file.
+//!  - `--example`: the example input in `data/examples`.
+//!  - `--example-part <part>`: the example input of a single part, e.g. `data/examples/01-2.txt`.
+//!  - `-`: the input is read from stdin.
+//!  - `--input-name <name>`: a named input in `data/inputs/{day}/`.
+use std::{env, fmt::Display, fs, io, process};
+
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, read_file, read_file_part};
+
+pub const INPUT_ARG: &str = "--input";
+pub const INPUT_NAME_ARG: &str = "--input-name";
+pub const EXAMPLE_ARG: &str = "--example";
+pub const EXAMPLE_PART_ARG: &str = "--example-part";
+pub const STDIN_ARG: &str = "-";
+
+/// Source of the puzzle input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The real input in `data/inputs/{day}.txt`.
+    #[default]
+    Default,
+    /// A named input in `data/inputs/{day}/{name}.txt`.
+    Named(String),
+    /// An arbitrary file.
+    Path(String),
+    /// The example input in `data/examples/{day}.txt`.
+    Example,
+    /// The example input of a part in `data/examples/{day}-{part}.txt`.
+    ExamplePart(u8),
+    /// Standard input.
+    Stdin,
+}
+
+impl InputSource {
+    /// Select the input source from command-line arguments. At most one source may be given.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value_of = |arg: &str| -> Result<Option<String>, String> {
+            match args.iter().position(|x| x == arg) {
+                Some(index) => args
+                    .get(index + 1)
+                    .cloned()
+                    .map(Some)
+                    .ok_or(format!("`{arg}` expects a value.")),
+                None => Ok(None),
+            }
+        };
+
+        let example_part = value_of(EXAMPLE_PART_ARG)?
+            .map(|part| {
+                part.parse()
+                    .map_err(|_| format!("`{EXAMPLE_PART_ARG}` expects a part number."))
+            })
+            .transpose()?;
+
+        let sources: Vec<InputSource> = [
+            value_of(INPUT_ARG)?.map(InputSource::Path),
+            value_of(INPUT_NAME_ARG)?.map(InputSource::Named),
+            args.iter()
+                .any(|x| x == EXAMPLE_ARG)
+                .then_some(InputSource::Example),
+            example_part.map(InputSource::ExamplePart),
+            // `-` might also be the value of another argument, e.g. `--input -`.
+            args.iter()
+                .enumerate()
+                .any(|(i, x)| x == STDIN_ARG && (i == 0 || !takes_value(&args[i - 1])))
+                .then_some(InputSource::Stdin),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        match sources.as_slice() {
+            [] => Ok(InputSource::Default),
+            [source] => Ok(source.clone()),
+            _ => Err("only one input source can be selected.".into()),
+        }
+    }
+
+    /// Select the input source from the arguments of the current process, exits on invalid arguments.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+        InputSource::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("Unexpected command-line input: {e}");
+            process::exit(1);
+        })
+    }
+
+    /// The name of a named input.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            InputSource::Named(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Arguments that select this source in a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Default => vec![],
+            InputSource::Named(name) => vec![INPUT_NAME_ARG.into(), name.clone()],
+            InputSource::Path(path) => vec![INPUT_ARG.into(), path.clone()],
+            InputSource::Example => vec![EXAMPLE_ARG.into()],
+            InputSource::ExamplePart(part) => vec![EXAMPLE_PART_ARG.into(), part.to_string()],
+            InputSource::Stdin => vec![STDIN_ARG.into()],
+        }
+    }
+
+    /// Read the input of a day from this source.
+    #[must_use]
+    pub fn read(&self, day: Day) -> String {
+        match self {
+            InputSource::Default => read_file("inputs", day),
+            InputSource::Example => read_file("examples", day),
+            InputSource::ExamplePart(part) => read_file_part("examples", day, *part),
+            InputSource::Named(name) => read_path(&get_named_input_path(day, name)),
+            InputSource::Path(path) => read_path(path),
+            InputSource::Stdin => io::read_to_string(io::stdin()).expect("could not read stdin"),
+        }
+    }
+
+    /// Human-readable description of this source for a day.
+    #[must_use]
+    pub fn describe(&self, day: Day) -> String {
+        match self {
+            InputSource::Default => format!("default (data/inputs/{day}.txt)"),
+            InputSource::Named(name) => format!("{name} ({})", get_named_input_path(day, name)),
+            InputSource::Path(path) => path.clone(),
+            InputSource::Example => format!("example (data/examples/{day}.txt)"),
+            InputSource::ExamplePart(part) => {
+                format!("example part {part} (data/examples/{day}-{part}.txt)")
+            }
+            InputSource::Stdin => "stdin".into(),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Default => write!(f, "default"),
+            InputSource::Named(name) => write!(f, "{name}"),
+            InputSource::Path(path) => write!(f, "{path}"),
+            InputSource::Example => write!(f, "example"),
+            InputSource::ExamplePart(part) => write!(f, "example part {part}"),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+fn takes_value(arg: &str) -> bool {
+    [INPUT_ARG, INPUT_NAME_ARG, EXAMPLE_PART_ARG].contains(&arg)
+}
+
+fn read_path(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|_| panic!("could not open input file {path}"))
+}
+
+pub fn print_input_header(source: &InputSource, day: Day) {
+    println!("{ANSI_ITALIC}Input: {}{ANSI_RESET}", source.describe(day));
+}
+
+/// Helper function that reads the puzzle input of a day from the source selected on the command-line.
+/// The source is printed as a header unless it is the default input.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let source = InputSource::from_env();
+    if source != InputSource::Default {
+        print_input_header(&source, day);
+    }
+    source.read(day)
+}
+
+#[must_use]
+pub fn get_named_input_path(day: Day, name: &str) -> String {
+    format!("data/inputs/{day}/{name}.txt")
+}
+
+/// Names of all named inputs of a day, i.e. the files in `data/inputs/{day}/`, sorted alphabetically.
+#[must_use]
+pub fn named_inputs(day: Day) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(format!("data/inputs/{day}")) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let file_name = entry.ok()?.file_name().into_string().ok()?;
+            // expected answers live next to the inputs.
+            if file_name.ends_with(".answers.txt") {
+                return None;
+            }
+            file_name.strip_suffix(".txt").map(ToString::to_string)
+        })
+        .collect();
+
+    names.sort();
+    names
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::day;
+
+    fn parse(args: &[&str]) -> Result<InputSource, String> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        InputSource::from_args(&args)
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(parse(&["01", "--time"]), Ok(InputSource::Default));
+        assert_eq!(
+            parse(&["01", "--input", "edge.txt", "--time"]),
+            Ok(InputSource::Path("edge.txt".into()))
+        );
+        assert_eq!(
+            parse(&["01", "--input-name", "alice"]),
+            Ok(InputSource::Named("alice".into()))
+        );
+        assert_eq!(parse(&["01", "--example"]), Ok(InputSource::Example));
+        assert_eq!(
+            parse(&["01", "--example-part", "2"]),
+            Ok(InputSource::ExamplePart(2))
+        );
+        assert_eq!(parse(&["01", "-"]), Ok(InputSource::Stdin));
+    }
+
+    #[test]
+    fn rejects_invalid_sources() {
+        assert!(parse(&["01", "--input"]).is_err());
+        assert!(parse(&["01", "--example-part", "two"]).is_err());
+        assert!(parse(&["01", "--example", "-"]).is_err());
+        assert_eq!(
+            parse(&["01", "--input", "-"]),
+            Ok(InputSource::Path("-".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Default,
+            InputSource::Named("alice".into()),
+            InputSource::Path("edge.txt".into()),
+            InputSource::Example,
+            InputSource::ExamplePart(1),
+            InputSource::Stdin,
+        ] {
+            let args = source.to_args();
+            assert_eq!(InputSource::from_args(&args), Ok(source));
+        }
+    }
+
+    #[test]
+    fn describes_sources() {
+        assert_eq!(
+            InputSource::ExamplePart(2).describe(day!(1)),
+            "example part 2 (data/examples/01-2.txt)"
+        );
+        assert_eq!(
+            InputSource::Named("bob".into()).describe(day!(3)),
+            "bob (data/inputs/03/bob.txt)"
+        );
+    }
+}
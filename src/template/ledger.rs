@@ -0,0 +1,324 @@
+/// Ledger of submitted answers and the server's verdicts.
+/// Used to avoid resubmitting answers that are known to be wrong, which would cost a lockout timer.
+use std::{collections::HashMap, fmt::Display, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::aoc_client::Verdict;
+
+static LEDGER_FILE_PATH: &str = "./data/answers.json";
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attempt {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// All submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub data: Vec<Attempt>,
+}
+
+/// Reasons to refuse submitting an answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Refusal {
+    /// The part was already solved with the given answer.
+    AlreadySolved(String),
+    /// The answer was already submitted and rejected.
+    KnownWrong(Verdict),
+    /// The answer is not below an answer that is known to be too high.
+    AboveTooHigh(String),
+    /// The answer is not above an answer that is known to be too low.
+    BelowTooLow(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part is already solved with answer `{answer}`.")
+            }
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted ({verdict}).")
+            }
+            Refusal::AboveTooHigh(bound) => {
+                write!(
+                    f,
+                    "this answer is not below `{bound}`, which is known to be too high."
+                )
+            }
+            Refusal::BelowTooLow(bound) => {
+                write!(
+                    f,
+                    "this answer is not above `{bound}`, which is known to be too low."
+                )
+            }
+        }
+    }
+}
+
+impl Ledger {
+    /// Dehydrate the ledger to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(LEDGER_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the ledger from a JSON file. If not present, returns an empty ledger.
+    /// Unlike timings, a malformed ledger is an error so it does not get overwritten.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(LEDGER_FILE_PATH) {
+            Ok(content) => Ledger::try_from(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Record the verdict for a submitted answer.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        self.data.push(Attempt {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+
+    /// All attempts for a part.
+    pub fn attempts(&self, day: Day, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.data
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    /// The accepted answer of a part, if any.
+    pub fn correct_answer(&self, day: Day, part: u8) -> Option<&str> {
+        self.attempts(day, part)
+            .find(|a| a.verdict == Verdict::Correct)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Check whether an answer may be submitted.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        if let Some(correct) = self.correct_answer(day, part) {
+            return Err(Refusal::AlreadySolved(correct.to_string()));
+        }
+
+        if let Some(attempt) = self.attempts(day, part).find(|a| {
+            a.answer == answer
+                && matches!(
+                    a.verdict,
+                    Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow
+                )
+        }) {
+            return Err(Refusal::KnownWrong(attempt.verdict));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            self.attempts(day, part)
+                .filter(move |a| a.verdict == verdict)
+                .filter_map(|a| a.answer.parse::<i128>().ok())
+        };
+
+        if let Some(too_high) = bound(Verdict::TooHigh).min()
+            && value >= too_high
+        {
+            return Err(Refusal::AboveTooHigh(too_high.to_string()));
+        }
+
+        if let Some(too_low) = bound(Verdict::TooLow).max()
+            && value <= too_low
+        {
+            return Err(Refusal::BelowTooLow(too_low.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Ledger> for JsonValue {
+    fn from(value: Ledger) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Ledger {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Ledger {
+            data: json_data
+                .iter()
+                .map(Attempt::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Attempt> for JsonValue {
+    fn from(value: &Attempt) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Attempt {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected attempt to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected attempt.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected attempt.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Attempt {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ledger, Refusal};
+    use crate::{day, template::aoc_client::Verdict};
+    use tinyjson::JsonValue;
+
+    fn get_mock_ledger() -> Ledger {
+        let mut ledger = Ledger::default();
+        ledger.record(day!(1), 1, "100", Verdict::TooHigh);
+        ledger.record(day!(1), 1, "50", Verdict::TooLow);
+        ledger.record(day!(1), 1, "75", Verdict::Wrong);
+        ledger.record(day!(1), 1, "76", Verdict::RateLimited);
+        ledger.record(day!(2), 1, "42", Verdict::Correct);
+        ledger
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(day!(2), 1, "43"),
+            Err(Refusal::AlreadySolved("42".into()))
+        );
+        assert_eq!(ledger.check(day!(2), 2, "43"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(day!(1), 1, "75"),
+            Err(Refusal::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 1, "100"),
+            Err(Refusal::KnownWrong(Verdict::TooHigh))
+        );
+    }
+
+    #[test]
+    fn allows_rate_limited_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.check(day!(1), 1, "76"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(day!(1), 1, "120"),
+            Err(Refusal::AboveTooHigh("100".into()))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 1, "-3"),
+            Err(Refusal::BelowTooLow("50".into()))
+        );
+        assert_eq!(ledger.check(day!(1), 1, "51"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 1, "99"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 1, "not a number"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let ledger = get_mock_ledger();
+        let json = JsonValue::from(ledger.clone()).stringify().unwrap();
+        let parsed = Ledger::try_from(json).unwrap();
+        assert_eq!(parsed.data, ledger.data);
+        assert_eq!(parsed.correct_answer(day!(2), 1), Some("42"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_ledger() {
+        let json = r#"{ "data": [{ "day": "01", "part": 1, "answer": "1", "verdict": "maybe" }] }"#;
+        Ledger::try_from(json.to_string()).unwrap();
+    }
+}
@@ -0,0 +1,229 @@
+//! Wall-clock and memory limits for child solution runs, so that a runaway day can not block a whole run.
+//!
+//! The timeout applies per part: it restarts whenever the child reports a finished part. The memory
+//! limit caps the address space of the child via `RLIMIT_AS` and is only enforced on Linux.
+use std::{
+    io,
+    process::{Child, Command, ExitStatus},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::heap::format_bytes;
+use crate::template::report::Status;
+
+/// How often a running child is checked on.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// No limits are enforced unless configured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Time a single part may take, `None` to wait forever.
+    pub timeout: Option<Duration>,
+    /// Address space of the child process in bytes, `None` for no limit.
+    pub memory: Option<u64>,
+}
+
+/// How a child process ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exit {
+    Exited(ExitStatus),
+    /// The child was killed after a part exceeded the timeout.
+    TimedOut,
+}
+
+impl Limits {
+    /// Limits from command line values: a timeout in seconds, where `0` disables it, and a memory limit
+    /// in MiB.
+    pub fn from_args(timeout_secs: Option<f64>, memory_mib: Option<u64>) -> Result<Self, String> {
+        let timeout = match timeout_secs {
+            None | Some(0.0) => None,
+            Some(secs) => Some(
+                Duration::try_from_secs_f64(secs)
+                    .map_err(|_| format!("`--timeout` expects a positive number, got {secs}."))?,
+            ),
+        };
+
+        Ok(Limits {
+            timeout,
+            memory: memory_mib.map(|mib| mib.saturating_mul(1024 * 1024)),
+        })
+    }
+
+    /// Applies the memory limit to a command before it is spawned.
+    pub fn apply(&self, cmd: &mut Command) {
+        #[cfg(target_os = "linux")]
+        if let Some(bytes) = self.memory {
+            use std::os::unix::process::CommandExt;
+
+            // SAFETY: `setrlimit` is async-signal-safe and the closure does not allocate.
+            unsafe {
+                cmd.pre_exec(move || {
+                    let limit = libc::rlimit {
+                        rlim_cur: bytes,
+                        rlim_max: bytes,
+                    };
+                    if libc::setrlimit(libc::RLIMIT_AS, &raw const limit) == 0 {
+                        Ok(())
+                    } else {
+                        Err(io::Error::last_os_error())
+                    }
+                });
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = cmd;
+    }
+
+    /// Waits for a child to exit, killing it once no new part finished within the timeout.
+    /// `progress` counts the parts finished so far, e.g. by the size of the report file.
+    pub fn wait(&self, child: &mut Child, progress: impl Fn() -> u64) -> io::Result<Exit> {
+        let Some(timeout) = self.timeout else {
+            return child.wait().map(Exit::Exited);
+        };
+
+        let mut last_progress = (progress(), Instant::now());
+
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Exit::Exited(status));
+            }
+
+            let current = progress();
+            if current != last_progress.0 {
+                last_progress = (current, Instant::now());
+            } else if last_progress.1.elapsed() > timeout {
+                child.kill()?;
+                child.wait()?;
+                return Ok(Exit::TimedOut);
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// The status of parts that did not finish because the child was stopped by a limit.
+    /// A child that runs into the memory limit aborts after printing a failed allocation to stderr,
+    /// see [`is_allocation_failure`]. Other crashes are not attributed to a limit.
+    pub fn aborted_status(&self, exit: Exit, allocation_failed: bool) -> Option<Status> {
+        match exit {
+            Exit::TimedOut => Some(Status::TimedOut),
+            Exit::Exited(status)
+                if self.memory.is_some() && allocation_failed && !status.success() =>
+            {
+                Some(Status::OutOfMemory)
+            }
+            Exit::Exited(_) => None,
+        }
+    }
+
+    /// Describes why parts were aborted, e.g. `Timed out after 60.0s.`.
+    pub fn describe(&self, status: Status) -> String {
+        match (status, self.timeout, self.memory) {
+            (Status::TimedOut, Some(timeout), _) => format!("Timed out after {timeout:.1?}."),
+            (Status::OutOfMemory, _, Some(bytes)) => {
+                format!("Out of memory (limit {}).", format_bytes(bytes))
+            }
+            (status, _, _) => format!("Aborted: {status}."),
+        }
+    }
+}
+
+/// Returns `true` for the message the standard library prints to stderr before aborting on a failed
+/// allocation, e.g. `memory allocation of 1024 bytes failed`.
+pub fn is_allocation_failure(line: &str) -> bool {
+    line.starts_with("memory allocation of ") && line.ends_with(" bytes failed")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Exit, Limits, is_allocation_failure};
+    use crate::template::report::Status;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_limits() {
+        assert_eq!(Limits::from_args(None, None), Ok(Limits::default()));
+        assert_eq!(
+            Limits::from_args(Some(0.0), Some(64)),
+            Ok(Limits {
+                timeout: None,
+                memory: Some(64 * 1024 * 1024),
+            })
+        );
+        assert_eq!(
+            Limits::from_args(Some(1.5), None).unwrap().timeout,
+            Some(Duration::from_millis(1500))
+        );
+        assert!(Limits::from_args(Some(-1.0), None).is_err());
+        assert_eq!(Limits::default().timeout, None);
+    }
+
+    #[test]
+    fn describes_aborted_parts() {
+        let limits = Limits::from_args(Some(2.0), Some(1)).unwrap();
+        assert_eq!(limits.describe(Status::TimedOut), "Timed out after 2.0s.");
+        assert_eq!(
+            limits.describe(Status::OutOfMemory),
+            "Out of memory (limit 1.0 MiB)."
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kills_children_after_timeout() {
+        use std::process::Command;
+
+        let limits = Limits::from_args(Some(0.1), None).unwrap();
+        let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+        let exit = limits.wait(&mut child, || 0).unwrap();
+        assert_eq!(exit, Exit::TimedOut);
+        assert_eq!(limits.aborted_status(exit, false), Some(Status::TimedOut));
+
+        let mut child = Command::new("true").spawn().unwrap();
+        let exit = limits.wait(&mut child, || 0).unwrap();
+        assert!(matches!(exit, Exit::Exited(status) if status.success()));
+        assert_eq!(limits.aborted_status(exit, false), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn attributes_failed_allocations_to_memory_limit() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        // killed by SIGABRT.
+        let aborted = Exit::Exited(ExitStatus::from_raw(6));
+        let limits = Limits::from_args(None, Some(64)).unwrap();
+        assert_eq!(
+            limits.aborted_status(aborted, true),
+            Some(Status::OutOfMemory)
+        );
+        assert_eq!(limits.aborted_status(aborted, false), None);
+        assert_eq!(Limits::default().aborted_status(aborted, true), None);
+
+        assert!(is_allocation_failure(
+            "memory allocation of 1073741824 bytes failed"
+        ));
+        assert!(!is_allocation_failure(
+            "thread 'main' panicked at src/bin/01.rs:5:1:"
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn limits_memory_of_children() {
+        use std::process::Command;
+
+        let limits = Limits::from_args(None, Some(64)).unwrap();
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "ulimit -v"]);
+        limits.apply(&mut cmd);
+        let output = cmd.output().unwrap();
+        // `ulimit -v` reports the address space limit in KiB.
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "65536");
+    }
+}
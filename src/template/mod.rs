@@ -1,19 +1,33 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod heap;
 pub mod runner;
 
+pub use comparison::DEFAULT_THRESHOLD;
 pub use day::*;
+pub use input::{InputSource, read_input};
+pub use limits::Limits;
 
+mod comparison;
 mod day;
+mod input;
+mod ledger;
+mod limits;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod scaling;
+mod stats;
 mod timings;
+mod verify;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
@@ -39,6 +53,12 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A day can declare a parse function with `parse = <fn>`, e.g. `solution!(4, parse = parse)`.
+/// The input is then parsed once, timed separately, and both parts receive a reference to the parsed value.
+/// The parse function returns a `Result<T, ParseError>`, a malformed input is reported with its location.
+///
+/// With the `heap-stats` feature, the heap usage of every part is tracked by a counting global allocator.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,8 +70,40 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            declare_parts(&[$($part),*]);
+            let input = $crate::template::read_input(DAY);
+            $( run_part($func, &input, DAY, $part); )*
+        }
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            declare_parts(&[0, $($part),*]);
+            let input = $crate::template::read_input(DAY);
+            let parsed = run_parse($parse, input.as_str(), DAY);
+            $( run_part($func, &parsed, DAY, $part); )*
+        }
+    };
+
+    (@setup $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +111,8 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+        #[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::heap::CountingAlloc = $crate::template::heap::CountingAlloc;
     };
 }
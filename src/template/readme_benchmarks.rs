@@ -3,7 +3,8 @@
 use std::{fs, io};
 
 use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::heap::format_bytes;
+use crate::template::timings::{PartTiming, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -59,22 +60,38 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 | Peak heap |".into(),
+        "| :---: | :---: | :---: | :---: | :---:  |".into(),
     ];
 
+    let has_missing_heap = timings.data.iter().any(|t| t.peak_bytes().is_none());
+
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
+        let peak_heap = timing.peak_bytes().map_or_else(|| "-".into(), format_bytes);
+        // parts that were stopped by a limit show why they are missing.
+        let missing_part = timing
+            .aborted
+            .map_or("-".into(), |status| status.to_string());
+        let part = |part: &Option<PartTiming>, missing: &str| {
+            part.as_ref()
+                .map_or_else(|| missing.to_string(), |p| p.timing.clone())
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` | `{peak_heap}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            part(&timing.parse, "-"),
+            part(&timing.part_1, &missing_part),
+            part(&timing.part_2, &missing_part)
         ));
     }
 
     lines.push(String::new());
+    if has_missing_heap {
+        lines.push("Peak heap is `-` for days benched without the `heap-stats` feature.".into());
+        lines.push(String::new());
+    }
     lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
 
@@ -100,28 +117,68 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::heap::HeapStats,
+        template::report::Status,
+        template::timings::Timings,
+        template::timings::{PartTiming, Timing},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming::new("10ms")),
+                    part_2: Some(PartTiming::new("20ms")),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming::new("30ms")),
+                    part_2: Some(PartTiming::new("40ms")),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
+                },
+                Timing {
+                    part_1: Some(PartTiming::new("40ms")),
+                    part_2: Some(PartTiming::new("50ms")),
+                    total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
+                },
+            ],
+        }
+    }
+
+    fn get_aborted_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    part_1: Some(PartTiming::new("30ms")),
+                    total_nanos: 3e+10,
+                    aborted: Some(Status::TimedOut),
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    parse: Some(PartTiming::new("1ms")),
+                    part_1: Some(PartTiming {
+                        heap: Some(HeapStats {
+                            peak_bytes: 2048,
+                            total_bytes: 4096,
+                            allocations: 2,
+                        }),
+                        ..PartTiming::new("40ms")
+                    }),
+                    part_2: Some(PartTiming {
+                        heap: Some(HeapStats {
+                            peak_bytes: 512,
+                            total_bytes: 512,
+                            allocations: 1,
+                        }),
+                        ..PartTiming::new("50ms")
+                    }),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -167,11 +224,13 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 | Peak heap |",
+            "| :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` | `-` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` | `-` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` | `-` |",
+            "",
+            "Peak heap is `-` for days benched without the `heap-stats` feature.",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +239,26 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_aborted_benchmarks() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_aborted_timings(), 120.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 | Peak heap |",
+            "| :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `timed out` | `-` |",
+            "| [Day 4](./src/bin/04.rs) | `1ms` | `40ms` | `50ms` | `2.0 KiB` |",
+            "",
+            "Peak heap is `-` for days benched without the `heap-stats` feature.",
+            "",
+            "**Total: 120.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
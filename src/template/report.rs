@@ -0,0 +1,333 @@
+/// Machine-readable protocol used by solution binaries to report results to the parent `run_multi` process.
+/// When `AOC_REPORT_FILE` is set, a solution first appends a header line with the parts it declares,
+/// then every executed part appends one JSON line to that file.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::heap::HeapStats;
+use crate::template::stats::Stats;
+
+/// Name of the environment variable that selects the report file.
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part panicked, the other part still ran. Also used for parts that never ran because their
+    /// child process crashed or exited early.
+    Panicked,
+    /// The part did not finish before the timeout, its child process was killed.
+    TimedOut,
+    /// The part ran out of memory under the memory limit of its child process.
+    OutOfMemory,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Solved => write!(f, "solved"),
+            Status::Unsolved => write!(f, "unsolved"),
+            Status::Panicked => write!(f, "panicked"),
+            Status::TimedOut => write!(f, "timed out"),
+            Status::OutOfMemory => write!(f, "out of memory"),
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "panicked" => Ok(Status::Panicked),
+            "timed out" => Ok(Status::TimedOut),
+            "out of memory" => Ok(Status::OutOfMemory),
+            _ => Err(format!("unknown report status `{s}`.")),
+        }
+    }
+}
+
+/// Result record for a single part, as emitted by a solution binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// The part number, `0` for the parse step of solutions that declare one.
+    pub part: u8,
+    pub answer: Option<String>,
+    pub nanos: f64,
+    pub samples: u128,
+    pub status: Status,
+    /// Benchmark statistics, only present if the part was benched.
+    pub stats: Option<Stats>,
+    /// Heap usage, only present if the solution was built with the `heap-stats` feature.
+    pub heap: Option<HeapStats>,
+}
+
+impl Report {
+    /// Appends the report to the file selected via [`REPORT_FILE_ENV`], if any.
+    pub fn emit(&self) -> io::Result<()> {
+        append_line(&JsonValue::from(self))
+    }
+}
+
+/// Appends the header record with the parts a solution declares, where `0` is the parse step, to the
+/// file selected via [`REPORT_FILE_ENV`], if any.
+pub fn emit_declared_parts(parts: &[u8]) -> io::Result<()> {
+    let parts = parts
+        .iter()
+        .map(|&part| JsonValue::Number(f64::from(part)))
+        .collect();
+    let header = HashMap::from([("parts".to_string(), JsonValue::Array(parts))]);
+    append_line(&JsonValue::Object(header))
+}
+
+fn append_line(json: &JsonValue) -> io::Result<()> {
+    let Ok(path) = env::var(REPORT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let line = json
+        .stringify()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Contents of a report file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReportFile {
+    /// The parts the solution declares, [`None`] if it stopped before writing its header.
+    pub declared_parts: Option<Vec<u8>>,
+    pub reports: Vec<Report>,
+}
+
+/// Reads the header and all reports from a report file. A missing file yields no reports.
+pub fn read_reports(path: &Path) -> Result<ReportFile, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(ReportFile::default()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    parse_reports(&content)
+}
+
+/// Parses JSON lines into the header and reports, skipping blank lines.
+pub fn parse_reports(content: &str) -> Result<ReportFile, String> {
+    let mut file = ReportFile::default();
+
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let json = JsonValue::from_str(line).or(Err("report is not valid JSON."))?;
+        match json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|map| map.get("parts"))
+        {
+            Some(parts) => file.declared_parts = Some(parse_parts(parts)?),
+            None => file.reports.push(Report::try_from(&json)?),
+        }
+    }
+
+    Ok(file)
+}
+
+fn parse_parts(value: &JsonValue) -> Result<Vec<u8>, String> {
+    let error = || "Expected header.parts to be an array of numbers.".to_string();
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    value
+        .get::<Vec<JsonValue>>()
+        .ok_or_else(error)?
+        .iter()
+        .map(|part| part.get::<f64>().map(|x| *x as u8).ok_or_else(error))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Report> for JsonValue {
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Report {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u128)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let heap = match json.get("heap") {
+            Some(v) if !v.is_null() => Some(HeapStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(Report {
+            part,
+            answer: answer.cloned(),
+            nanos,
+            samples,
+            status,
+            stats,
+            heap,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Report, ReportFile, Status, parse_reports};
+    use crate::template::{heap::HeapStats, stats::Stats};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = Report {
+            part: 1,
+            answer: Some("@ @ ( ) ms\n(2s @ 5 samples)".into()),
+            nanos: 74.0,
+            samples: 3,
+            status: Status::Solved,
+            stats: Stats::from_durations(&[
+                Duration::from_nanos(70),
+                Duration::from_nanos(74),
+                Duration::from_nanos(80),
+            ]),
+            heap: Some(HeapStats {
+                peak_bytes: 1024,
+                total_bytes: 4096,
+                allocations: 7,
+            }),
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(parse_reports(&line).unwrap().reports, vec![report]);
+    }
+
+    #[test]
+    fn parses_unsolved_reports() {
+        let reports = parse_reports(
+            "{\"part\":2,\"answer\":null,\"nanos\":0,\"samples\":1,\"status\":\"unsolved\"}\n\n",
+        )
+        .unwrap()
+        .reports;
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].answer, None);
+        assert_eq!(reports[0].status, Status::Unsolved);
+        assert_eq!(reports[0].stats, None);
+        assert_eq!(reports[0].heap, None);
+    }
+
+    #[test]
+    fn parses_declared_parts() {
+        let file = parse_reports(
+            "{\"parts\":[0,1,2]}\n{\"part\":0,\"answer\":null,\"nanos\":5,\"samples\":1,\"status\":\"solved\"}",
+        )
+        .unwrap();
+        assert_eq!(file.declared_parts, Some(vec![0, 1, 2]));
+        assert_eq!(file.reports.len(), 1);
+        assert_eq!(file.reports[0].part, 0);
+
+        assert_eq!(parse_reports("").unwrap(), ReportFile::default());
+        assert!(parse_reports("{\"parts\":[\"1\"]}").is_err());
+    }
+
+    #[test]
+    fn roundtrips_statuses() {
+        for status in [
+            Status::Solved,
+            Status::Unsolved,
+            Status::Panicked,
+            Status::TimedOut,
+            Status::OutOfMemory,
+        ] {
+            assert_eq!(status.to_string().parse::<Status>(), Ok(status));
+        }
+        assert!("crashed".parse::<Status>().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_reports() {
+        parse_reports("{\"part\":1}").unwrap();
+    }
+}
@@ -1,14 +1,131 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, io, path::Path};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET, Day, InputSource, Limits,
+    aoc_client::get_input_path,
+    input::{named_inputs, print_input_header},
+    report::{Report, Status},
+};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{PartTiming, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// How a day fared, from best to worst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Outcome {
+    /// Every part that ran returned an answer.
+    Solved,
+    /// There is no solution bin for the day yet.
+    NotScaffolded,
+    /// A part returned `None`, or the day reported no results at all, e.g. because it does not compile.
+    Unsolved,
+    /// A part was stopped by the timeout or the memory limit.
+    Aborted,
+    /// A part panicked, or the run crashed before it finished.
+    Panicked,
+    /// The day could not be run, e.g. because its bin could not be built or its report is malformed.
+    Failed,
+}
+
+impl Outcome {
+    /// The worst outcome of the reports of a run.
+    pub fn from_reports(reports: &[Report]) -> Self {
+        reports
+            .iter()
+            .map(|report| match report.status {
+                Status::Solved => Outcome::Solved,
+                Status::Unsolved => Outcome::Unsolved,
+                Status::Panicked => Outcome::Panicked,
+                Status::TimedOut | Status::OutOfMemory => Outcome::Aborted,
+            })
+            .max()
+            .unwrap_or(Outcome::Unsolved)
+    }
+
+    /// `1` if a part failed, `2` if a part is unsolved, `0` otherwise.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Outcome::Solved | Outcome::NotScaffolded => 0,
+            Outcome::Unsolved => 2,
+            Outcome::Aborted | Outcome::Panicked | Outcome::Failed => 1,
+        }
+    }
+}
+
+/// A part that panicked or was stopped by a limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure {
+    pub day: Day,
+    /// The named input, `None` for the default input.
+    pub input: Option<String>,
+    /// The part number, `0` for the parse step.
+    pub part: u8,
+    pub status: Status,
+}
+
+/// Result of running several days.
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+    /// Timings of the default inputs, only collected for timed runs.
+    pub timings: Option<Timings>,
+    /// The worst outcome of every day, across all inputs.
+    pub outcomes: Vec<(Day, Outcome)>,
+    pub failures: Vec<Failure>,
+}
+
+impl Summary {
+    /// The exit code of the worst outcome, see [`Outcome::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        self.outcomes
+            .iter()
+            .map(|(_, outcome)| *outcome)
+            .max()
+            .map_or(0, Outcome::exit_code)
+    }
+
+    /// Prints how many days had each outcome, followed by every failed part.
+    pub fn print(&self) {
+        let count = |outcome| self.outcomes.iter().filter(|(_, o)| *o == outcome).count();
+        println!(
+            "\n{ANSI_BOLD}Days:{ANSI_RESET} {} solved, {} unsolved, {} panicked, {} aborted, {} failed to run, {} not scaffolded",
+            count(Outcome::Solved),
+            count(Outcome::Unsolved),
+            count(Outcome::Panicked),
+            count(Outcome::Aborted),
+            count(Outcome::Failed),
+            count(Outcome::NotScaffolded),
+        );
+
+        for failure in &self.failures {
+            let part = match failure.part {
+                0 => "Parse".to_string(),
+                part => format!("Part {part}"),
+            };
+            let input = failure
+                .input
+                .as_ref()
+                .map_or(String::new(), |name| format!(" (input `{name}`)"));
+            println!(
+                "{ANSI_RED}Day {} {part}{input}: {}{ANSI_RESET}",
+                failure.day, failure.status
+            );
+        }
+    }
+}
+
+/// Run the given days. With `all_inputs`, every named input of a day is run after the default input.
+/// Timings are only collected for the default input.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    all_inputs: bool,
+    limits: &Limits,
+) -> Summary {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut summary = Summary::default();
 
     let mut need_space = false;
 
@@ -24,14 +141,51 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            if !Path::new(&get_path_for_bin(day)).exists() {
+                println!("Not scaffolded.");
+                summary.outcomes.push((day, Outcome::NotScaffolded));
+                return;
+            }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            let inputs = get_inputs_to_run(day, all_inputs);
+            let mut outcome = Outcome::Solved;
+
+            for input in &inputs {
+                print_input_label(day, input, inputs.len());
+
+                let reports =
+                    match child_commands::run_solution(day, is_timed, is_release, input, limits) {
+                        Ok(reports) => reports,
+                        Err(e) => {
+                            eprintln!("Could not run day {day}: {e}");
+                            outcome = outcome.max(Outcome::Failed);
+                            continue;
+                        }
+                    };
+
+                outcome = outcome.max(Outcome::from_reports(&reports));
+                summary.failures.extend(
+                    reports
+                        .iter()
+                        .filter(|report| {
+                            !matches!(report.status, Status::Solved | Status::Unsolved)
+                        })
+                        .map(|report| Failure {
+                            day,
+                            input: input.name().map(ToString::to_string),
+                            part: report.part,
+                            status: report.status,
+                        }),
+                );
+
+                if reports.is_empty() {
+                    println!("Not solved.");
+                } else if *input == InputSource::Default {
+                    timings.push(child_commands::collect_timing(&reports, day));
+                }
             }
+
+            summary.outcomes.push((day, outcome));
         });
 
     if is_timed {
@@ -40,17 +194,28 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        summary.timings = Some(timings);
     }
+
+    summary.print();
+    summary
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Report(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture the output of the solution."),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Report(e) => write!(f, "invalid result report: {e}"),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -64,98 +229,285 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// Inputs to run a day against.
+/// With `all_inputs`, named inputs follow the default input, which is skipped if it does not exist.
+#[must_use]
+pub fn get_inputs_to_run(day: Day, all_inputs: bool) -> Vec<InputSource> {
+    let named = if all_inputs {
+        named_inputs(day)
+    } else {
+        vec![]
+    };
+
+    if named.is_empty() {
+        return vec![InputSource::Default];
+    }
+
+    let default = Path::new(&get_input_path(day))
+        .exists()
+        .then_some(InputSource::Default);
+
+    default
+        .into_iter()
+        .chain(named.into_iter().map(InputSource::Named))
+        .collect()
+}
+
+/// Label the default input when running a day against several inputs.
+/// Solution binaries print the header of all other inputs themselves.
+pub fn print_input_label(day: Day, input: &InputSource, input_count: usize) {
+    if input_count > 1 && *input == InputSource::Default {
+        print_input_header(&InputSource::Default, day);
+    }
+}
+
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their result reports.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::{
+        ANSI_RED, ANSI_RESET, Day, InputSource, Limits,
+        limits::{Exit, is_allocation_failure},
+        report::{REPORT_FILE_ENV, Report, Status, read_reports},
+        stats::format_timing,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        env, fs,
+        io::{self, BufRead, BufReader},
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
+        str::FromStr,
         thread,
     };
+    use tinyjson::JsonValue;
+
+    /// Run the solution bin for a given day against an input and return the reports it emitted.
+    /// The bin is built first, so that neither compiling nor cargo itself count towards the limits.
+    /// Parts that were stopped by a limit or by the child exiting early are reported as failed, see
+    /// [`run_child`].
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        input: &InputSource,
+        limits: &Limits,
+    ) -> Result<Vec<Report>, Error> {
+        let input_args = input.to_args();
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        // a day that does not compile has no reports.
+        let Some(executable) = build_solution(day, is_release)? else {
+            return Ok(vec![]);
+        };
 
-        if is_release {
-            args.push("--release");
-        }
+        let mut args = vec![];
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        args.extend(input_args.iter().map(String::as_str));
+
+        // results are reported through a side-channel file, the child's stdout is meant for humans.
+        let report_path = get_report_path(day);
+        let _ = fs::remove_file(&report_path);
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = Command::new(executable);
+        cmd.args(&args).env(REPORT_FILE_ENV, &report_path);
+
+        run_child(cmd, &report_path, limits)
+    }
+
+    /// Run a child that reports to `report_path` and return its reports once it exited.
+    /// If the child did not exit successfully, every declared part that did not report is added with the
+    /// status of the limit that stopped it, or as panicked if it crashed, aborted or exited early.
+    fn run_child(
+        mut cmd: Command,
+        report_path: &Path,
+        limits: &Limits,
+    ) -> Result<Vec<Report>, Error> {
+        // spawn child command with piped stdout/stderr.
+        // forward output to stdout/stderr.
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        limits.apply(&mut cmd);
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let mut child = cmd.spawn()?;
 
-        let mut output = vec![];
+        let stdout = BufReader::new(child.stdout.take().ok_or(super::Error::BrokenPipe)?);
+        let stderr = BufReader::new(child.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        let stdout_thread = thread::spawn(move || {
+            stdout.lines().map_while(Result::ok).for_each(|line| {
+                println!("{line}");
             });
         });
+        // a failed allocation tells running out of memory apart from other crashes.
+        let stderr_thread = thread::spawn(move || {
+            stderr
+                .lines()
+                .map_while(Result::ok)
+                .fold(false, |failed, line| {
+                    eprintln!("{line}");
+                    failed || is_allocation_failure(&line)
+                })
+        });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        // every finished part appends a line to the report file.
+        let exit = limits.wait(&mut child, || {
+            fs::metadata(report_path).map_or(0, |metadata| metadata.len())
+        })?;
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        stdout_thread.join().unwrap();
+        let allocation_failed = stderr_thread.join().unwrap();
 
-        Ok(output)
-    }
+        let file = read_reports(report_path).map_err(Error::Report);
+        let _ = fs::remove_file(report_path);
+        let file = file?;
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+        let aborted = match (limits.aborted_status(exit, allocation_failed), exit) {
+            (Some(status), _) => Some((status, limits.describe(status))),
+            // e.g. a segfault, an abort, or an exit after a malformed input.
+            (None, Exit::Exited(status)) if !status.success() => {
+                Some((Status::Panicked, format!("Exited early ({status}).")))
+            }
+            (None, _) => None,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
+        match aborted {
+            Some((status, message)) => {
+                println!("{ANSI_RED}{message}{ANSI_RESET}");
+                let parts = file.declared_parts.unwrap_or_default();
+                Ok(with_aborted_parts(file.reports, status, &parts))
+            }
+            None => Ok(file.reports),
+        }
+    }
+
+    /// Adds a report with the given status for every declared part that did not report before the child
+    /// was stopped.
+    pub fn with_aborted_parts(
+        mut reports: Vec<Report>,
+        status: Status,
+        parts: &[u8],
+    ) -> Vec<Report> {
+        for &part in parts {
+            if !reports.iter().any(|report| report.part == part) {
+                reports.push(Report {
+                    part,
+                    answer: None,
+                    nanos: 0_f64,
+                    samples: 0,
+                    status,
+                    stats: None,
+                    heap: None,
+                });
+            }
+        }
+        reports
+    }
+
+    /// Builds the solution bin for a day and returns the path of its executable as reported by cargo,
+    /// which takes the profile, the target and the target directory into account. [`None`] if the bin
+    /// does not compile, the compiler errors are forwarded as is.
+    fn build_solution(day: Day, is_release: bool) -> Result<Option<PathBuf>, Error> {
+        let day_padded = day.to_string();
+        let mut build_args = vec![
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+            "--bin",
+            &day_padded,
+        ];
+
+        if is_release {
+            build_args.push("--release");
+        }
+
+        // solutions are built with the same opt-in heap tracking as the runner.
+        if cfg!(feature = "heap-stats") {
+            build_args.extend(["--features", "heap-stats"]);
+        }
+
+        let output = Command::new("cargo")
+            .args(&build_args)
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        find_executable(&String::from_utf8_lossy(&output.stdout), day)
+            .map(Some)
+            .ok_or_else(|| {
+                Error::IO(io::Error::other(format!(
+                    "cargo did not report an executable for day {day}."
+                )))
+            })
+    }
+
+    /// The executable of a day's bin among the JSON messages of `cargo build --message-format=json`.
+    pub fn find_executable(messages: &str, day: Day) -> Option<PathBuf> {
+        let day = day.to_string();
+
+        messages
+            .lines()
+            .filter_map(|line| JsonValue::from_str(line).ok())
+            .find_map(|message| {
+                let message = message.get::<HashMap<String, JsonValue>>()?;
+                let name = message
+                    .get("target")?
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("name")?
+                    .get::<String>()?;
+
+                if message.get("reason")?.get::<String>()? != "compiler-artifact" || *name != day {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+                message
+                    .get("executable")?
+                    .get::<String>()
+                    .map(PathBuf::from)
+            })
+    }
+
+    fn get_report_path(day: Day) -> PathBuf {
+        env::temp_dir().join(format!("aoc-report-{}-{day}.jsonl", process::id()))
+    }
+
+    /// Collect the timings of all solved parts of a day, and whether parts were stopped by a limit.
+    pub fn collect_timing(reports: &[Report], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day);
+
+        timings.aborted = reports
+            .iter()
+            .map(|report| report.status)
+            .find(|status| matches!(status, Status::TimedOut | Status::OutOfMemory));
+
+        reports
+            .iter()
+            .filter(|report| report.status == Status::Solved)
+            .for_each(|report| {
+                // benched parts are timed by their median, which is less sensitive to outliers.
+                let nanos = report.stats.map_or(report.nanos, |s| s.median);
+                let part = super::PartTiming {
+                    timing: format_timing(nanos),
+                    stats: report.stats,
+                    heap: report.heap,
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                match report.part {
+                    0 => timings.parse = Some(part),
+                    1 => timings.part_1 = Some(part),
+                    2 => timings.part_2 = Some(part),
+                    _ => {}
                 }
 
                 timings.total_nanos += nanos;
@@ -164,31 +516,6 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +532,213 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{collect_timing, find_executable, run_child, with_aborted_parts};
 
         use crate::day;
+        use crate::template::report::{Report, Status};
+        use crate::template::run_multi::{Outcome, Summary};
+        use crate::template::stats::Stats;
+        use std::{path::PathBuf, time::Duration};
+
+        fn report(part: u8, answer: Option<&str>, nanos: f64, samples: u128) -> Report {
+            Report {
+                part,
+                answer: answer.map(Into::into),
+                nanos,
+                samples,
+                status: if answer.is_some() {
+                    Status::Solved
+                } else {
+                    Status::Unsolved
+                },
+                stats: None,
+                heap: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = collect_timing(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), 74.13, 100_000),
+                    report(2, Some("10"), 74_130_000.0, 99_999),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1.unwrap().timing, "74.13ns");
+            assert_eq!(res.part_2.unwrap().timing, "74.13ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answer() {
+            let res = collect_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(1, Some("@ @ @ ( ) ms\n(1s @ 5 samples)"), 2e9, 5),
+                    report(2, Some("10s"), 1e8, 1),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().timing, "2s");
+            assert_eq!(res.part_2.unwrap().timing, "100ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
+        fn collects_median_of_benched_parts() {
+            let mut benched = report(1, Some("1"), 10.0, 3);
+            benched.stats = Stats::from_durations(&[
+                Duration::from_nanos(10),
+                Duration::from_nanos(12),
+                Duration::from_nanos(1_000),
+            ]);
+            let res = collect_timing(&[benched], day!(1));
+            assert_approx_eq!(res.total_nanos, 12_f64);
+            let part_1 = res.part_1.unwrap();
+            assert_eq!(part_1.timing, "12ns");
+            assert_eq!(part_1.stats.unwrap().samples, 3);
+        }
+
+        #[test]
+        fn collects_parse_times() {
+            let mut parse = report(0, None, 50.0, 1);
+            parse.status = Status::Solved;
+            let res = collect_timing(&[parse, report(1, Some("1"), 100.0, 1)], day!(1));
+            assert_approx_eq!(res.total_nanos, 150_f64);
+            assert_eq!(res.parse.unwrap().timing, "50ns");
+            assert_eq!(res.part_1.unwrap().timing, "100ns");
+        }
+
+        #[test]
+        fn collects_missing_parts() {
+            let res = collect_timing(
+                &[report(1, None, 10.0, 1), report(2, None, 10.0, 1)],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn collects_aborted_parts() {
+            let reports = with_aborted_parts(
+                vec![report(1, Some("1"), 100.0, 1)],
+                Status::OutOfMemory,
+                &[1, 2],
+            );
+            assert_eq!(reports.len(), 2);
+            assert_eq!(reports[1].part, 2);
+            assert_eq!(reports[1].status, Status::OutOfMemory);
+
+            // only declared parts are reported, including a parse step that did not finish.
+            let parts: Vec<u8> = with_aborted_parts(vec![], Status::TimedOut, &[0, 1])
+                .iter()
+                .map(|report| report.part)
+                .collect();
+            assert_eq!(parts, vec![0, 1]);
+
+            let res = collect_timing(&reports, day!(1));
+            assert_approx_eq!(res.total_nanos, 100_f64);
+            assert_eq!(res.part_1.unwrap().timing, "100ns");
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.aborted, Some(Status::OutOfMemory));
+            assert_eq!(collect_timing(&reports[..1], day!(1)).aborted, None);
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn collects_parts_of_crashed_children() {
+            use crate::template::{Limits, report::REPORT_FILE_ENV};
+            use std::{env, process::Command};
+
+            let report_path =
+                env::temp_dir().join(format!("aoc-report-{}-crashed.jsonl", std::process::id()));
+            let report = r#"{"part":1,"answer":"1","nanos":10,"samples":1,"status":"solved"}"#;
+
+            // declares both parts, reports part 1 and aborts before part 2.
+            let mut cmd = Command::new("sh");
+            cmd.args([
+                "-c",
+                &format!(
+                    r#"echo '{{"parts":[1,2]}}' >> "$AOC_REPORT_FILE"; echo '{report}' >> "$AOC_REPORT_FILE"; kill -ABRT $$"#
+                ),
+            ])
+            .env(REPORT_FILE_ENV, &report_path);
+
+            let reports = run_child(cmd, &report_path, &Limits::default()).unwrap();
+            assert_eq!(reports.len(), 2);
+            assert_eq!(reports[0].status, Status::Solved);
+            assert_eq!((reports[1].part, reports[1].status), (2, Status::Panicked));
+            assert_eq!(Outcome::from_reports(&reports), Outcome::Panicked);
+            assert_eq!(report_path.exists(), false);
+        }
+
+        #[test]
+        fn finds_executables() {
+            let messages = [
+                r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code"},"executable":null}"#,
+                r#"{"reason":"compiler-artifact","target":{"name":"01"},"executable":"/tmp/target/x86_64-unknown-linux-gnu/dhat/01"}"#,
+                r#"{"reason":"build-finished","success":true}"#,
+            ]
+            .join("\n");
+
+            assert_eq!(
+                find_executable(&messages, day!(1)),
+                Some(PathBuf::from(
+                    "/tmp/target/x86_64-unknown-linux-gnu/dhat/01"
+                ))
+            );
+            assert_eq!(find_executable(&messages, day!(2)), None);
+        }
+
+        #[test]
+        fn distinguishes_outcomes() {
+            let solved = report(1, Some("1"), 1.0, 1);
+            let unsolved = report(2, None, 1.0, 1);
+            let mut panicked = report(2, None, 1.0, 1);
+            panicked.status = Status::Panicked;
+            let mut timed_out = report(2, None, 0.0, 0);
+            timed_out.status = Status::TimedOut;
+
+            assert_eq!(Outcome::from_reports(&[solved.clone()]), Outcome::Solved);
+            assert_eq!(
+                Outcome::from_reports(&[solved.clone(), unsolved]),
+                Outcome::Unsolved
+            );
+            assert_eq!(
+                Outcome::from_reports(&[solved.clone(), timed_out]),
+                Outcome::Aborted
+            );
+            assert_eq!(
+                Outcome::from_reports(&[panicked, solved]),
+                Outcome::Panicked
+            );
+            assert_eq!(Outcome::from_reports(&[]), Outcome::Unsolved);
+        }
+
+        #[test]
+        fn exits_with_worst_outcome() {
+            let summary = |outcomes: &[Outcome]| Summary {
+                outcomes: outcomes.iter().map(|&o| (day!(1), o)).collect(),
+                ..Summary::default()
+            };
+
+            assert_eq!(summary(&[]).exit_code(), 0);
+            assert_eq!(
+                summary(&[Outcome::Solved, Outcome::NotScaffolded]).exit_code(),
+                0
+            );
+            assert_eq!(
+                summary(&[Outcome::Solved, Outcome::Unsolved]).exit_code(),
+                2
+            );
+            assert_eq!(
+                summary(&[Outcome::Panicked, Outcome::Unsolved]).exit_code(),
+                1
+            );
+            assert_eq!(summary(&[Outcome::Aborted]).exit_code(), 1);
+            assert_eq!(summary(&[Outcome::Solved, Outcome::Failed]).exit_code(), 1);
+        }
     }
 }
@@ -2,55 +2,253 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{
+    cell::{Cell, RefCell},
+    cmp, env, process,
+};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::parse::ParseError;
+use crate::template::heap::{self, HeapStats};
+use crate::template::ledger::Ledger;
+use crate::template::report::{self, Report, Status};
+use crate::template::stats::{Stats, format_nanos};
+use crate::template::{ANSI_BOLD, ANSI_RED};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, InputSource, aoc_client};
 
+/// Run a part of a solution. The answer can be of any type that implements [`Display`], it is
+/// reported, compared and submitted as text. A panicking part is reported as failed, so that the
+/// other part still runs.
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let timed =
+        catch_panic(|| run_timed(&func, input, |result| print_result(result, &part_str, "")));
 
+    let (result, duration, stats, heap) = match timed {
+        Ok(timed) => timed,
+        Err(panic) => {
+            print_panic(&part_str, &panic);
+            emit_report(&Report {
+                part,
+                answer: None,
+                nanos: 0_f64,
+                samples: 0,
+                status: Status::Panicked,
+                stats: None,
+                heap: None,
+            });
+            return;
+        }
+    };
+
+    let samples = stats.map_or(1, |s| s.samples);
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
+    if let (Some(stats), Some(_)) = (&stats, &result) {
+        print_stats(stats);
+    }
+    if let (Some(heap), Some(_)) = (&heap, &result) {
+        print_heap(heap);
+    }
+
+    emit_report(&Report {
+        part,
+        answer: result.as_ref().map(ToString::to_string),
+        #[allow(clippy::cast_precision_loss)]
+        nanos: duration.as_nanos() as f64,
+        samples,
+        status: if result.is_some() {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        },
+        stats,
+        heap,
+    });
+
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run the parse step of a solution and return the parsed input.
+/// Parsing is timed (and benched) separately from the parts and reported as part `0`.
+/// If the input is malformed, the error is printed as a diagnostic and the process exits.
+/// A panicking parse step is reported before exiting, the parts can not run without its result.
+pub fn run_parse<I: Copy, T>(func: impl Fn(I) -> Result<T, ParseError>, input: I, day: Day) -> T {
+    let timed = catch_panic(|| {
+        run_timed(&func, input, |result| match result {
+            Ok(_) => print!("Parse:"),
+            Err(e) => {
+                eprintln!("{}", e.clone().with_day(day).diagnostic());
+                process::exit(1);
+            }
+        })
+    });
+
+    let (parsed, duration, stats, heap) = match timed {
+        Ok(timed) => timed,
+        Err(panic) => {
+            print_panic("Parse", &panic);
+            emit_report(&Report {
+                part: 0,
+                answer: None,
+                nanos: 0_f64,
+                samples: 0,
+                status: Status::Panicked,
+                stats: None,
+                heap: None,
+            });
+            process::exit(1);
+        }
+    };
+
+    let Ok(parsed) = parsed else {
+        unreachable!("parse errors exit before benching");
+    };
+
+    let samples = stats.map_or(1, |s| s.samples);
+    print!("\r");
+    println!("Parse:{}", format_duration(&duration, samples));
+
+    if let Some(stats) = &stats {
+        print_stats(stats);
+    }
+    if let Some(heap) = &heap {
+        print_heap(heap);
+    }
+
+    emit_report(&Report {
+        part: 0,
+        answer: None,
+        #[allow(clippy::cast_precision_loss)]
+        nanos: duration.as_nanos() as f64,
+        samples,
+        status: Status::Solved,
+        stats,
+        heap,
+    });
+
+    parsed
+}
+
+/// Declare the parts a solution runs, where `0` is the parse step, before running any of them.
+/// The parent process reports parts that never finished, e.g. because the process crashed, as failed.
+pub fn declare_parts(parts: &[u8]) {
+    if let Err(e) = report::emit_declared_parts(parts) {
+        eprintln!("Failed to write result report: {e}");
+    }
+}
+
+fn emit_report(report: &Report) {
+    if let Err(e) = report.emit() {
+        eprintln!("Failed to write result report: {e}");
+    }
+}
+
+/// Message and location of a caught panic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Panic {
+    pub message: String,
+    /// Source location, e.g. `src/bin/05.rs:12:5`.
+    pub location: Option<String>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    /// Whether panics of the current thread are recorded instead of being printed.
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<Panic>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook that records panics of threads inside [`catch_panic`] and forwards all other
+/// panics to the hook that was installed before. The hook is installed once per process.
+fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+
+    INSTALLED.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CAPTURING.try_with(Cell::get).unwrap_or(false) {
+                return previous_hook(info);
+            }
+            let panic = Panic {
+                message: info.payload_as_str().unwrap_or("Box<dyn Any>").to_string(),
+                location: info.location().map(ToString::to_string),
+            };
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(panic));
+        }));
+    });
+}
+
+/// Runs `func` and catches a panic instead of unwinding further. While it runs, panics of the current
+/// thread are recorded instead of being printed, other threads still use the previous hook.
+pub fn catch_panic<R>(func: impl FnOnce() -> R) -> Result<R, Panic> {
+    install_panic_hook();
+
+    let was_capturing = CAPTURING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CAPTURING.set(was_capturing);
+
+    result.map_err(|_| {
+        LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or(Panic {
+                message: "unknown panic".into(),
+                location: None,
+            })
+    })
+}
+
+fn print_panic(part: &str, panic: &Panic) {
+    print!("\r");
+    println!("{part}: {ANSI_RED}✖ {panic}{ANSI_RESET}");
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When benched, the returned duration is the median of all samples.
+/// The heap usage is measured for the first execution only.
 fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
+    func: &impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<Stats>, Option<HeapStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, heap) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        heap::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if std::env::args().any(|x| x == "--time") {
+        let stats = bench(func, input, &base_time);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let median = Duration::from_nanos(stats.median.round() as u64);
+        (result, median, Some(stats), heap)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None, heap)
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(func: &impl Fn(I) -> T, input: I, base_time: &Duration) -> Stats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -59,6 +257,12 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm caches and branch predictors before sampling, these runs are discarded.
+    let warmup_iterations = (bench_iterations / 10).max(1);
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input)));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -67,19 +271,7 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Stats::from_durations(&timers).expect("bench collects at least one sample")
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -90,6 +282,21 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+fn print_stats(stats: &Stats) {
+    println!(
+        "        {ANSI_ITALIC}min {} · p95 {} · σ {} · 95% CI [{}, {}]{ANSI_RESET}",
+        format_nanos(stats.min),
+        format_nanos(stats.p95),
+        format_nanos(stats.std_dev),
+        format_nanos(stats.ci_low),
+        format_nanos(stats.ci_high),
+    );
+}
+
+fn print_heap(heap: &HeapStats) {
+    println!("        {ANSI_ITALIC}heap: {}{ANSI_RESET}", heap.summary());
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -127,12 +334,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the answer ledger does not rule the answer out.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_client::Submission, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,13 +363,76 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+    let source = InputSource::from_env();
+    if source != InputSource::Default {
+        println!("Not submitting: answers of the {source} input are never submitted.");
+        return None;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+
+    let mut ledger = match Ledger::read_from_file() {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Could not read answer ledger, not submitting: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = ledger.check(day, part, &answer) {
+        println!("Not submitting: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let submission = aoc_client::submit(day, part, &answer);
+
+    match &submission {
+        Ok(submission) => {
+            println!("{}", submission.message);
+            ledger.record(day, part, &answer, submission.verdict);
+            if let Err(e) = ledger.store_file() {
+                eprintln!("Failed to store answer ledger: {e}");
+            }
+        }
+        Err(e) => eprintln!("Failed to submit result: {e}"),
+    }
+
+    Some(submission)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Panic, catch_panic};
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let line = line!() + 1;
+        let panic = catch_panic(|| -> u32 { panic!("no {} for you", "answer") }).unwrap_err();
+        assert_eq!(panic.message, "no answer for you");
+        let location = panic.location.clone().unwrap();
+        assert!(location.starts_with(&format!("{}:{line}:", file!())));
+        assert_eq!(
+            panic.to_string(),
+            format!("panicked at {location}: no answer for you")
+        );
+
+        let panic = catch_panic(|| Option::<u32>::None.unwrap()).unwrap_err();
+        assert!(panic.message.contains("`None`"));
+
+        // nested calls keep capturing until the outermost call returns.
+        let inner = catch_panic(|| catch_panic(|| panic!("inner")).unwrap_err().message);
+        assert_eq!(inner.as_deref(), Ok("inner"));
+        assert!(!super::CAPTURING.with(std::cell::Cell::get));
+
+        let panic = Panic {
+            message: "boom".into(),
+            location: None,
+        };
+        assert_eq!(panic.to_string(), "panicked: boom");
+    }
 }
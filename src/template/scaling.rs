@@ -0,0 +1,262 @@
+//! Benchmarks a day across generated inputs of growing size and estimates how its running time scales.
+//!
+//! The exponent `k` of `time ≈ c · size^k` is fitted by least squares on a log-log scale. It is an
+//! empirical estimate: `1.0` suggests linear scaling, `2.0` quadratic scaling.
+use std::{fs, path::Path};
+
+use crate::generate::{generator, get_generated_path};
+use crate::property::Rng;
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, InputSource, Limits, run_multi::child_commands,
+    stats::format_nanos, timings::Timings,
+};
+
+/// Input sizes that are benched by default, doubling each time.
+pub const DEFAULT_SIZES: [usize; 5] = [125, 250, 500, 1000, 2000];
+
+/// Seed of the generated inputs, so that repeated runs bench the same inputs.
+const SEED: u64 = 0;
+
+/// Timings of a day, one per input size.
+#[derive(Clone, Debug)]
+pub struct Scaling {
+    pub day: Day,
+    /// What the sizes count, e.g. `"rows"`.
+    pub unit: &'static str,
+    pub sizes: Vec<usize>,
+    /// The timing of every size, in the same order as `sizes`.
+    pub timings: Timings,
+}
+
+/// Generates an input of every size, then benches the day on each of them.
+pub fn run(day: Day, sizes: &[usize], limits: &Limits) -> Result<Scaling, String> {
+    let generator = generator(day).ok_or(format!("day {day} has no input generator"))?;
+
+    let mut scaling = Scaling {
+        day,
+        unit: generator.unit(),
+        sizes: vec![],
+        timings: Timings::default(),
+    };
+
+    for &size in sizes {
+        let path = get_generated_path(day, size);
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(&path, generator.generate(&mut Rng::new(SEED), size))
+            .map_err(|e| format!("could not write {path}: {e}"))?;
+
+        println!("{ANSI_BOLD}Size {size}{ANSI_RESET}");
+        let reports =
+            child_commands::run_solution(day, true, true, &InputSource::Path(path), limits)
+                .map_err(|e| format!("could not run day {day}: {e:?}"))?;
+
+        if reports.is_empty() {
+            println!("Not solved.");
+            continue;
+        }
+
+        let timing = child_commands::collect_timing(&reports, day);
+        let is_aborted = timing.aborted.is_some();
+        scaling.sizes.push(size);
+        scaling.timings.data.push(timing);
+
+        // larger inputs would only run into the same limit.
+        if is_aborted {
+            break;
+        }
+    }
+
+    Ok(scaling)
+}
+
+impl Scaling {
+    /// Parts with at least one timing, the parse step is part `0`.
+    pub fn parts(&self) -> Vec<u8> {
+        (0..=2)
+            .filter(|&part| {
+                self.timings
+                    .data
+                    .iter()
+                    .any(|t| t.part_nanos(part).is_some())
+            })
+            .collect()
+    }
+
+    /// The fitted exponent of a part, if it was timed for at least two sizes.
+    pub fn exponent(&self, part: u8) -> Option<f64> {
+        let points: Vec<(f64, f64)> = self
+            .sizes
+            .iter()
+            .zip(&self.timings.data)
+            .filter_map(|(&size, timing)| Some((size as f64, timing.part_nanos(part)?)))
+            .collect();
+
+        fit_exponent(&points)
+    }
+
+    /// Prints a table of the median time per size and part, followed by the fitted exponents.
+    pub fn print(&self) {
+        let parts = self.parts();
+        let label = |part: u8| match part {
+            0 => "Parse".to_string(),
+            part => format!("Part {part}"),
+        };
+
+        println!(
+            "{ANSI_BOLD}Scaling of day {}{ANSI_RESET} (size in {})",
+            self.day, self.unit
+        );
+        println!("----------");
+
+        let header: String = parts
+            .iter()
+            .map(|&p| format!(" {:>10}", label(p)))
+            .collect();
+        println!("{:>8}{header}", "Size");
+
+        for (size, timing) in self.sizes.iter().zip(&self.timings.data) {
+            let row: String = parts
+                .iter()
+                .map(|&part| {
+                    let nanos = timing.part_nanos(part).map_or("-".into(), format_nanos);
+                    format!(" {nanos:>10}")
+                })
+                .collect();
+            println!("{size:>8}{row}");
+        }
+
+        let exponents: String = parts
+            .iter()
+            .map(|&part| match self.exponent(part) {
+                Some(k) => format!(" {:>10}", format!("n^{k:.2}")),
+                None => format!(" {:>10}", "-"),
+            })
+            .collect();
+        println!("{ANSI_ITALIC}{:>8}{exponents}{ANSI_RESET}", "Fit");
+    }
+
+    /// The timings as CSV, one row per size with the median nanoseconds of every part.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("day,size,parse_nanos,part_1_nanos,part_2_nanos\n");
+
+        for (size, timing) in self.sizes.iter().zip(&self.timings.data) {
+            let cells: Vec<String> = (0..=2)
+                .map(|part| {
+                    timing
+                        .part_nanos(part)
+                        .map_or(String::new(), |n| n.to_string())
+                })
+                .collect();
+            csv.push_str(&format!("{},{size},{}\n", self.day, cells.join(",")));
+        }
+
+        csv
+    }
+}
+
+/// Fits `time = c · size^k` to `(size, time)` points and returns `k`. Points that can't be placed on
+/// a log scale are ignored, at least two distinct sizes are needed.
+pub fn fit_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(size, time)| *size > 0.0 && *time > 0.0)
+        .map(|(size, time)| (size.ln(), time.ln()))
+        .collect();
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (logs.len() >= 2 && variance > 0.0).then(|| covariance / variance)
+}
+
+/// Writes the CSV of several days to a file.
+pub fn store_csv(path: &str, scalings: &[Scaling]) -> Result<(), std::io::Error> {
+    let mut csv = String::new();
+    for (i, scaling) in scalings.iter().enumerate() {
+        let rows = scaling.to_csv();
+        // keep the header of the first day only.
+        let skip = if i == 0 {
+            0
+        } else {
+            rows.find('\n').map_or(0, |i| i + 1)
+        };
+        csv.push_str(&rows[skip..]);
+    }
+    fs::write(path, csv)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Scaling, fit_exponent};
+    use crate::day;
+    use crate::template::{
+        Day,
+        stats::{Stats, format_nanos},
+        timings::{PartTiming, Timing, Timings},
+    };
+    use std::time::Duration;
+
+    fn timing(day: Day, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+        let part = |nanos: Option<f64>| {
+            nanos.map(|n| PartTiming {
+                stats: Stats::from_durations(&[Duration::from_nanos(n as u64)]),
+                ..PartTiming::new(format_nanos(n))
+            })
+        };
+
+        Timing {
+            part_1: part(part_1),
+            part_2: part(part_2),
+            total_nanos: part_1.unwrap_or(0.0) + part_2.unwrap_or(0.0),
+            ..Timing::new(day)
+        }
+    }
+
+    #[test]
+    fn fits_exponents() {
+        let linear: Vec<(f64, f64)> = [10.0, 20.0, 40.0].iter().map(|&n| (n, 3.0 * n)).collect();
+        assert!((fit_exponent(&linear).unwrap() - 1.0).abs() < 1e-9);
+
+        let quadratic: Vec<(f64, f64)> = [10.0, 100.0, 1000.0]
+            .iter()
+            .map(|&n| (n, 0.5 * n * n))
+            .collect();
+        assert!((fit_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+
+        assert_eq!(fit_exponent(&[(10.0, 5.0)]), None);
+        assert_eq!(fit_exponent(&[(10.0, 5.0), (10.0, 6.0)]), None);
+        assert_eq!(fit_exponent(&[]), None);
+    }
+
+    #[test]
+    fn collects_parts() {
+        let day = day!(4);
+        let scaling = Scaling {
+            day,
+            unit: "rows",
+            sizes: vec![100, 200],
+            timings: Timings {
+                data: vec![
+                    timing(day, Some(1000.0), None),
+                    timing(day, Some(4000.0), None),
+                ],
+            },
+        };
+
+        assert_eq!(scaling.parts(), vec![1]);
+        assert!((scaling.exponent(1).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(scaling.exponent(2), None);
+        assert_eq!(
+            scaling.to_csv(),
+            "day,size,parse_nanos,part_1_nanos,part_2_nanos\n04,100,,1000,\n04,200,,4000,\n"
+        );
+    }
+}
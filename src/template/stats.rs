@@ -0,0 +1,218 @@
+/// Summary statistics over a set of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// z-score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Statistics of a benchmark run. All durations are in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: f64,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub std_dev: f64,
+    /// Lower bound of the 95% confidence interval of the mean.
+    pub ci_low: f64,
+    /// Upper bound of the 95% confidence interval of the mean.
+    pub ci_high: f64,
+}
+
+impl Stats {
+    /// Computes statistics for a set of samples, returns [`None`] if there are none.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_durations(durations: &[Duration]) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = durations.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let n = nanos.len();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+
+        let median = if n.is_multiple_of(2) {
+            f64::midpoint(nanos[n / 2 - 1], nanos[n / 2])
+        } else {
+            nanos[n / 2]
+        };
+
+        let std_dev = if n > 1 {
+            let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        let margin = Z_95 * std_dev / (n as f64).sqrt();
+
+        Some(Stats {
+            samples: n as u128,
+            mean,
+            min: nanos[0],
+            median,
+            p95: percentile(&nanos, 95),
+            std_dev,
+            ci_low: (mean - margin).max(0.0),
+            ci_high: mean + margin,
+        })
+    }
+}
+
+/// Nearest-rank percentile of sorted values.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn percentile(sorted: &[f64], p: u8) -> f64 {
+    let rank = (f64::from(p) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Formats nanoseconds the same way [`Duration`]'s debug output does.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Formats nanoseconds for stored timings with up to two decimals in the largest fitting unit, e.g.
+/// `74.13ns` or `2s`. Unlike [`format_nanos`], fractions of a nanosecond are kept.
+pub fn format_timing(nanos: f64) -> String {
+    // rounding first, so that e.g. `999.999ns` moves up to `1µs` instead of printing as `1000ns`.
+    let round = |value: f64| (value * 100.0).round() / 100.0;
+    let (value, unit) = [(1.0, "ns"), (1e3, "µs"), (1e6, "ms")]
+        .into_iter()
+        .map(|(scale, unit)| (round(nanos / scale), unit))
+        .find(|(value, _)| *value < 1000.0)
+        .unwrap_or((round(nanos / 1e9), "s"));
+    let rounded = format!("{value:.2}");
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+    format!("{trimmed}{unit}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("ci_low".into(), JsonValue::Number(value.ci_low));
+        map.insert("ci_high".into(), JsonValue::Number(value.ci_high));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Stats {
+            samples: number("samples")? as u128,
+            mean: number("mean")?,
+            min: number("min")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            std_dev: number("std_dev")?,
+            ci_low: number("ci_low")?,
+            ci_high: number("ci_high")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Stats, format_nanos, format_timing};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn durations(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_durations(&[]), None);
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_durations(&durations(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.p95, 5.0);
+        assert!((stats.std_dev - 2.5_f64.sqrt()).abs() < 1e-9);
+        assert!(stats.ci_low < stats.mean && stats.mean < stats.ci_high);
+    }
+
+    #[test]
+    fn median_is_robust_to_outliers() {
+        let stats = Stats::from_durations(&durations(&[10, 10, 11, 12, 10_000])).unwrap();
+        assert_eq!(stats.median, 11.0);
+        assert!(stats.mean > 2000.0);
+    }
+
+    #[test]
+    fn computes_even_median() {
+        let stats = Stats::from_durations(&durations(&[1, 2, 3, 4])).unwrap();
+        assert_eq!(stats.median, 2.5);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_durations(&durations(&[7])).unwrap();
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.ci_low, 7.0);
+        assert_eq!(stats.ci_high, 7.0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Stats::from_durations(&durations(&[5, 1, 4, 2, 3])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json).unwrap(), stats);
+    }
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(74_130_000.0), "74.1ms");
+        assert_eq!(format_nanos(2e9), "2.0s");
+    }
+
+    #[test]
+    fn formats_timings() {
+        assert_eq!(format_timing(74.13), "74.13ns");
+        assert_eq!(format_timing(74_130_000.0), "74.13ms");
+        assert_eq!(format_timing(12_300.0), "12.3µs");
+        assert_eq!(format_timing(1e8), "100ms");
+        assert_eq!(format_timing(2e9), "2s");
+        assert_eq!(format_timing(999.999), "1µs");
+        assert_eq!(format_timing(999_995_000.0), "1s");
+    }
+}
@@ -2,16 +2,35 @@ use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
 use crate::template::Day;
+use crate::template::heap::HeapStats;
+use crate::template::report::Status;
+use crate::template::stats::Stats;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
 /// Represents benchmark times for a single day.
+/// `total_nanos` is based on the median of the benchmark samples.
+/// `parse` is only present for solutions that declare a parse function.
+/// `aborted` is set if missing parts were stopped by a timeout or the memory limit of the run.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    pub aborted: Option<Status>,
+}
+
+/// Benchmark times of the parse step or a single part.
+/// Stats are missing for timings stored before they were tracked, heap stats are only present for
+/// solutions built with the `heap-stats` feature.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartTiming {
+    /// The median duration, formatted for the readme, e.g. `1.5ms`.
+    pub timing: String,
+    pub stats: Option<Stats>,
+    pub heap: Option<HeapStats>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +70,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -65,6 +84,78 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Find the timing of a given day.
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.data.iter().find(|t| t.day == day)
+    }
+}
+
+impl Timing {
+    /// A timing of a day without any timed parts.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            aborted: None,
+        }
+    }
+
+    /// The timing of a part, part `0` is the parse step.
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            0 => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Median duration of a part in nanoseconds, part `0` is the parse step.
+    /// Falls back to the formatted duration for timings stored before statistics were tracked.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        let part = self.part(part)?;
+        match part.stats {
+            Some(stats) => Some(stats.median),
+            None => parse_duration(&part.timing),
+        }
+    }
+
+    /// The largest peak heap usage of the parse step and both parts.
+    pub fn peak_bytes(&self) -> Option<u64> {
+        (0..=2)
+            .filter_map(|part| self.part(part)?.heap)
+            .map(|heap| heap.peak_bytes)
+            .max()
+    }
+}
+
+impl PartTiming {
+    /// A part timing without stats.
+    pub fn new(timing: impl Into<String>) -> Self {
+        PartTiming {
+            timing: timing.into(),
+            ..PartTiming::default()
+        }
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parse a duration formatted with `{:.1?}` into nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s.trim() {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -114,23 +205,34 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        // parts are stored flat, e.g. `part_1`, `part_1_stats` and `part_1_heap`.
+        for (key, part) in [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ] {
+            let part = part.as_ref();
+            map.insert(
+                key.into(),
+                part.map_or(JsonValue::Null, |p| JsonValue::String(p.timing.clone())),
+            );
+            map.insert(
+                format!("{key}_stats"),
+                part.and_then(|p| p.stats.as_ref())
+                    .map_or(JsonValue::Null, JsonValue::from),
+            );
+            map.insert(
+                format!("{key}_heap"),
+                part.and_then(|p| p.heap.as_ref())
+                    .map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "aborted".into(),
+            value.aborted.map_or(JsonValue::Null, |status| {
+                JsonValue::String(status.to_string())
+            }),
         );
 
         JsonValue::Object(map)
@@ -151,26 +253,59 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let part = |key: &str| -> Result<Option<PartTiming>, String> {
+            let timing = match json.get(key) {
+                // parse timings are optional since only some solutions declare a parse step.
+                None if key == "parse" => None,
+                value => value
+                    .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                    .ok_or(format!("Expected timing.{key} to be null or string."))?,
+            };
+            let Some(timing) = timing else {
+                return Ok(None);
+            };
+
+            // stats are optional to stay compatible with timings stored before they were tracked.
+            let stats = match json.get(&format!("{key}_stats")) {
+                Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+                _ => None,
+            };
+
+            // heap stats are optional as well, solutions may be built without the `heap-stats` feature.
+            let heap = match json.get(&format!("{key}_heap")) {
+                Some(v) if !v.is_null() => Some(HeapStats::try_from(v)?),
+                _ => None,
+            };
+
+            Ok(Some(PartTiming {
+                timing: timing.clone(),
+                stats,
+                heap,
+            }))
+        };
+
+        // only present for days that were stopped by a limit.
+        let aborted = match json.get("aborted") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.aborted to be null or string.")?
+                    .parse::<Status>()?,
+            ),
+            _ => None,
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse: part("parse")?,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
             total_nanos,
+            aborted,
         })
     }
 }
@@ -181,35 +316,40 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming::new("10ms")),
+                    part_2: Some(PartTiming::new("20ms")),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming::new("30ms")),
+                    part_2: Some(PartTiming::new("40ms")),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1: Some(PartTiming::new("40ms")),
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{
+                report::Status,
+                timings::{PartTiming, Timings},
+            },
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -218,9 +358,34 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(PartTiming::new("1ms")));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.parse, None);
+            assert_eq!(timing.aborted, None);
+        }
+
+        #[test]
+        fn handles_json_aborted_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "aborted": "timed out" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.aborted, Some(Status::TimedOut));
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn handles_json_timing_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "2ns", "part_2": null, "total_nanos": 2,
+                "part_1_stats": { "samples": 3, "mean": 2, "min": 1, "median": 2, "p95": 3, "std_dev": 1, "ci_low": 0.9, "ci_high": 3.1 },
+                "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.as_ref().unwrap().stats.unwrap();
+            assert_eq!(stats.samples, 3);
+            assert_eq!(stats.median, 2.0);
+            assert_eq!(stats.p95, 3.0);
+            assert_eq!(timing.part_2, None);
         }
 
         #[test]
@@ -271,49 +436,85 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(PartTiming::new("1ms")),
+                    part_2: Some(PartTiming::new("2ms")),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1: Some(PartTiming::new("1ms")),
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
+            let timings = Timings {
+                data: vec![Timing::new(day!(1))],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod part_nanos {
+        use crate::{
+            day,
+            template::{
+                stats::Stats,
+                timings::{PartTiming, Timing, Timings},
+            },
+        };
+        use std::time::Duration;
+
+        #[test]
+        fn prefers_stored_median() {
+            let timing = Timing {
+                part_1: Some(PartTiming {
+                    stats: Stats::from_durations(&[Duration::from_nanos(900)]),
+                    ..PartTiming::new("1.0ms")
+                }),
+                total_nanos: 1_000_000_f64,
+                ..Timing::new(day!(1))
+            };
+
+            assert_eq!(timing.part_nanos(1), Some(900_f64));
+            assert_eq!(timing.part_nanos(2), None);
+        }
+
+        #[test]
+        fn parses_legacy_timings() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    part_1: Some(PartTiming::new("53.4µs")),
+                    part_2: Some(PartTiming::new("9.3ms")),
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let timing = timings.get(day!(1)).unwrap();
+            assert!((timing.part_nanos(1).unwrap() - 53_400_f64).abs() < 1e-6);
+            assert!((timing.part_nanos(2).unwrap() - 9_300_000_f64).abs() < 1e-6);
+            assert!(timings.get(day!(2)).is_none());
         }
     }
 
@@ -329,12 +530,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +545,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 
@@ -0,0 +1,254 @@
+/// Checks the results of a solution against the accepted answers of a day.
+use std::{fs, io, str::FromStr};
+
+use crate::template::ledger::Ledger;
+use crate::template::report::{Report, Status};
+use crate::template::{ANSI_GREEN, ANSI_RED, ANSI_RESET, Day};
+
+/// Path of the expected answers of the default input or a named input of a day.
+#[must_use]
+pub fn get_answers_path(day: Day, input: Option<&str>) -> String {
+    match input {
+        Some(name) => format!("data/inputs/{day}/{name}.answers.txt"),
+        None => format!("data/inputs/{day}.answers.txt"),
+    }
+}
+
+/// Expected answers of a day.
+/// Stored next to the input as one `<part>: <answer>` line per part, e.g. `1: 1234`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Read the answers file of an input. A missing file yields no answers.
+    pub fn read(day: Day, input: Option<&str>) -> Result<Self, String> {
+        match fs::read_to_string(get_answers_path(day, input)) {
+            Ok(content) => content.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Fill parts without an expected answer with the answer the ledger knows to be correct.
+    #[must_use]
+    pub fn or_ledger(self, ledger: &Ledger, day: Day) -> Self {
+        let from_ledger = |part| ledger.correct_answer(day, part).map(ToString::to_string);
+        Answers {
+            part_1: self.part_1.or_else(|| from_ledger(1)),
+            part_2: self.part_2.or_else(|| from_ledger(2)),
+        }
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or(format!("expected `<part>: <answer>`, got `{line}`."))?;
+
+            let answer = Some(answer.trim().to_string());
+            match part.trim() {
+                "1" => answers.part_1 = answer,
+                "2" => answers.part_2 = answer,
+                part => return Err(format!("unknown part `{part}`.")),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/// Result of checking a single part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The part produced the expected answer.
+    Pass,
+    /// The part produced a different answer, or none at all.
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// There is no expected answer to check against.
+    Missing { actual: Option<String> },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartVerification {
+    pub day: Day,
+    /// The named input, `None` for the default input.
+    pub input: Option<String>,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+/// Check the reports of a solution run against the expected answers.
+pub fn verify(
+    day: Day,
+    input: Option<&str>,
+    reports: &[Report],
+    answers: &Answers,
+) -> Vec<PartVerification> {
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            let actual = reports
+                .iter()
+                .find(|report| report.part == part && report.status == Status::Solved)
+                .and_then(|report| report.answer.clone());
+
+            let outcome = match answers.get(part) {
+                None => Outcome::Missing { actual },
+                Some(expected) if actual.as_deref() == Some(expected) => Outcome::Pass,
+                Some(expected) => Outcome::Fail {
+                    expected: expected.to_string(),
+                    actual,
+                },
+            };
+
+            PartVerification {
+                day,
+                input: input.map(ToString::to_string),
+                part,
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Print the outcome of each part.
+pub fn print(verifications: &[PartVerification]) {
+    for verification in verifications {
+        let part = verification.part;
+        match &verification.outcome {
+            Outcome::Pass => println!("Part {part}: {ANSI_GREEN}✔ pass{ANSI_RESET}"),
+            Outcome::Fail { expected, actual } => println!(
+                "Part {part}: {ANSI_RED}✖ fail{ANSI_RESET} (expected `{expected}`, got {})",
+                actual
+                    .as_ref()
+                    .map_or("no answer".into(), |x| format!("`{x}`"))
+            ),
+            Outcome::Missing { .. } => println!("Part {part}: ? missing expected answer"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Outcome, verify};
+    use crate::day;
+    use crate::template::aoc_client::Verdict;
+    use crate::template::ledger::Ledger;
+    use crate::template::report::{Report, Status};
+
+    fn report(part: u8, answer: Option<&str>) -> Report {
+        Report {
+            part,
+            answer: answer.map(Into::into),
+            nanos: 1.0,
+            samples: 1,
+            status: if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            },
+            stats: None,
+            heap: None,
+        }
+    }
+
+    #[test]
+    fn parses_answers() {
+        let answers: Answers = "1: 1234\n\n2:  abc \n".parse().unwrap();
+        assert_eq!(answers.get(1), Some("1234"));
+        assert_eq!(answers.get(2), Some("abc"));
+
+        let answers: Answers = "2: 5".parse().unwrap();
+        assert_eq!(answers.get(1), None);
+
+        assert!("3: 5".parse::<Answers>().is_err());
+        assert!("1234".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn falls_back_to_ledger() {
+        let mut ledger = Ledger::default();
+        ledger.record(day!(1), 1, "1", Verdict::Correct);
+        ledger.record(day!(1), 2, "2", Verdict::Correct);
+
+        let answers = Answers {
+            part_1: Some("10".into()),
+            part_2: None,
+        }
+        .or_ledger(&ledger, day!(1));
+
+        assert_eq!(answers.get(1), Some("10"));
+        assert_eq!(answers.get(2), Some("2"));
+    }
+
+    #[test]
+    fn verifies_parts() {
+        let answers = Answers {
+            part_1: Some("1".into()),
+            part_2: Some("2".into()),
+        };
+
+        let res = verify(
+            day!(1),
+            None,
+            &[report(1, Some("1")), report(2, Some("3"))],
+            &answers,
+        );
+        assert_eq!(res[0].outcome, Outcome::Pass);
+        assert_eq!(
+            res[1].outcome,
+            Outcome::Fail {
+                expected: "2".into(),
+                actual: Some("3".into())
+            }
+        );
+    }
+
+    #[test]
+    fn fails_unsolved_parts() {
+        let answers = Answers {
+            part_1: Some("1".into()),
+            part_2: Some("2".into()),
+        };
+
+        let res = verify(day!(1), None, &[report(0, None), report(1, None)], &answers);
+        assert!(
+            res.iter()
+                .all(|x| matches!(x.outcome, Outcome::Fail { actual: None, .. }))
+        );
+    }
+
+    #[test]
+    fn reports_missing_answers() {
+        let res = verify(day!(1), None, &[report(1, Some("1"))], &Answers::default());
+        assert_eq!(
+            res[0].outcome,
+            Outcome::Missing {
+                actual: Some("1".into())
+            }
+        );
+        assert_eq!(res[1].outcome, Outcome::Missing { actual: None });
+    }
+}